@@ -0,0 +1,405 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Timing harness measuring the parsing and each part of the solutions.
+//!
+//! Every stage is run a few times as warmup before its samples are collected,
+//! and the samples are summarized as min/median/p95.
+//! The results can be saved as a JSON baseline, to be compared against by later runs:
+//!
+//! ```json
+//! {
+//!   "15/input/two": { "min_ns": 41234567, "median_ns": 42345678, "p95_ns": 45678901 }
+//! }
+//! ```
+
+use crate::input::InputProvider;
+use crate::registry::{Day, Part};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Copy, Clone)]
+pub struct BenchOptions {
+    /// Number of untimed runs before collecting samples
+    pub warmup: u32,
+    /// Number of timed runs
+    pub iterations: u32,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "p95_ns", with = "nanos")]
+    pub p95: Duration,
+}
+
+#[derive(Debug)]
+pub struct Measurement {
+    pub day: u8,
+    pub input: String,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+/// Previously saved measurements, keyed by `day/input/stage`
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baseline(BTreeMap<String, Stats>);
+
+/// Measurement whose median is slower than the baseline by more than the allowed threshold
+#[derive(Debug)]
+pub struct Regression<'a> {
+    pub measurement: &'a Measurement,
+    pub baseline: Stats,
+    /// Relative change of the median compared to the baseline, in percent
+    pub change: f64,
+}
+
+impl Stage {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Solve(part) => part.name(),
+        }
+    }
+}
+
+impl Stats {
+    /// Summarizes a set of samples
+    ///
+    /// # Panics
+    /// If there are no samples
+    #[must_use]
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarize");
+
+        samples.sort_unstable();
+
+        let len = samples.len();
+        let median = if len.is_multiple_of(2) {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2
+        } else {
+            samples[len / 2]
+        };
+        // Nearest-rank percentile
+        let p95 = samples[(len * 95).div_ceil(100) - 1];
+
+        Self {
+            min: samples[0],
+            median,
+            p95,
+        }
+    }
+}
+
+impl Measurement {
+    #[must_use]
+    pub fn key(&self) -> String {
+        format!("{}/{}/{}", self.day, self.input, self.stage.name())
+    }
+}
+
+/// Relative change between two durations in percent, unless the first one is too short to compare against
+fn relative_change(before: Duration, after: Duration) -> Option<f64> {
+    (!before.is_zero()).then(|| (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0)
+}
+
+/// Times a closure, running it `warmup` times before collecting `iterations` samples
+fn time<T>(
+    options: BenchOptions,
+    mut f: impl FnMut() -> anyhow::Result<T>,
+) -> anyhow::Result<Stats> {
+    for _ in 0..options.warmup {
+        black_box(f()?);
+    }
+
+    let samples = (0..options.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            let result = f()?;
+            let elapsed = start.elapsed();
+            black_box(result);
+            Ok(elapsed)
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(Stats::from_samples(samples))
+}
+
+/// Measures the parsing and the requested parts of a day for one of its inputs
+///
+/// # Errors
+/// If the input cannot be read, parsed or solved an error is returned
+pub fn bench_day(
+    day: &Day,
+    inputs: &impl InputProvider,
+    input: &str,
+    parts: &[Part],
+    options: BenchOptions,
+) -> anyhow::Result<Vec<Measurement>> {
+    let content = day.read_input(inputs, input)?;
+    let mut measurements = Vec::with_capacity(parts.len() + 1);
+
+    let mut measurement = |stage, stats| {
+        measurements.push(Measurement {
+            day: day.number,
+            input: input.to_string(),
+            stage,
+            stats,
+        });
+    };
+
+    measurement(Stage::Parse, time(options, || day.parse(&content))?);
+
+    let parsed = day.parse(&content)?;
+
+    for &part in parts {
+        measurement(
+            Stage::Solve(part),
+            time(options, || day.solve(part, parsed.as_ref()))?,
+        );
+    }
+
+    Ok(measurements)
+}
+
+impl Baseline {
+    /// Loads a baseline previously written by [`Baseline::save`]
+    ///
+    /// # Errors
+    /// If the file cannot be read or is not a valid baseline an error is returned
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("reading baseline {}", path.display()))?;
+
+        serde_json::from_str(&content)
+            .with_context(|| format!("invalid baseline {}", path.display()))
+    }
+
+    /// Writes the baseline as JSON
+    ///
+    /// # Errors
+    /// If the file cannot be written an error is returned
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+
+        std::fs::write(path, content)
+            .with_context(|| format!("writing baseline {}", path.display()))
+    }
+
+    #[must_use]
+    pub fn get(&self, measurement: &Measurement) -> Option<Stats> {
+        self.0.get(&measurement.key()).copied()
+    }
+
+    /// Merges new measurements into the baseline, replacing the previous ones of the same stage
+    pub fn record<'a>(&mut self, measurements: impl IntoIterator<Item = &'a Measurement>) {
+        for measurement in measurements {
+            self.0.insert(measurement.key(), measurement.stats);
+        }
+    }
+
+    /// Lists the measurements whose median is slower than the baseline by more than `threshold` percent.
+    ///
+    /// Measurements missing from the baseline, or whose baseline median is zero, are never considered regressions.
+    #[must_use]
+    pub fn regressions<'a>(
+        &self,
+        measurements: &'a [Measurement],
+        threshold: f64,
+    ) -> Vec<Regression<'a>> {
+        measurements
+            .iter()
+            .filter_map(|measurement| {
+                let baseline = self.get(measurement)?;
+                let change = relative_change(baseline.median, measurement.stats.median)?;
+
+                Some(Regression {
+                    measurement,
+                    baseline,
+                    change,
+                })
+            })
+            .filter(|regression| regression.change > threshold)
+            .collect()
+    }
+}
+
+/// Table of measurements, with their change from a baseline when one is given
+pub struct BenchTable<'a> {
+    pub measurements: &'a [Measurement],
+    pub baseline: Option<&'a Baseline>,
+}
+
+impl Display for BenchTable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>3}  {:<10} {:<5} {:>12} {:>12} {:>12}",
+            "Day", "Input", "Stage", "Min", "Median", "P95"
+        )?;
+
+        if self.baseline.is_some() {
+            write!(f, " {:>9}", "Change")?;
+        }
+
+        for measurement in self.measurements {
+            let Stats { min, median, p95 } = measurement.stats;
+
+            write!(
+                f,
+                "\n{:>3}  {:<10} {:<5} {:>12} {:>12} {:>12}",
+                measurement.day,
+                measurement.input,
+                measurement.stage.name(),
+                format!("{:.2?}", min),
+                format!("{:.2?}", median),
+                format!("{:.2?}", p95),
+            )?;
+
+            if let Some(baseline) = self.baseline {
+                match baseline
+                    .get(measurement)
+                    .map(|before| relative_change(before.median, median))
+                {
+                    Some(Some(change)) => write!(f, " {:>+8.1}%", change)?,
+                    Some(None) => write!(f, " {:>9}", "n/a")?,
+                    None => write!(f, " {:>9}", "new")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Serializes durations as a whole number of nanoseconds
+mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos().try_into().unwrap_or(u64::MAX))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn stats_of_odd_samples() {
+        let stats = Stats::from_samples(ms(&[5, 1, 3]));
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn stats_of_even_samples() {
+        let stats = Stats::from_samples(ms(&(1..=20).rev().collect::<Vec<_>>()));
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(10_500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+    }
+
+    #[test]
+    fn baseline_flags_regressions() {
+        let measurement = |stage, median| Measurement {
+            day: 7,
+            input: "input".to_string(),
+            stage,
+            stats: Stats::from_samples(ms(&[median])),
+        };
+
+        let mut baseline = Baseline::default();
+        baseline.record(&[
+            measurement(Stage::Parse, 10),
+            measurement(Stage::Solve(Part::One), 10),
+        ]);
+
+        let baseline: Baseline =
+            serde_json::from_str(&serde_json::to_string(&baseline).unwrap()).unwrap();
+
+        let current = [
+            measurement(Stage::Parse, 11),
+            measurement(Stage::Solve(Part::One), 12),
+            measurement(Stage::Solve(Part::Two), 100),
+        ];
+
+        let regressions = baseline.regressions(&current, 15.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].measurement.key(), "7/input/one");
+        assert!((regressions[0].change - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zero_baseline_is_not_compared() {
+        let measurement = |median| Measurement {
+            day: 7,
+            input: "input".to_string(),
+            stage: Stage::Parse,
+            stats: Stats::from_samples(vec![median]),
+        };
+
+        let mut baseline = Baseline::default();
+        baseline.record(&[measurement(Duration::ZERO)]);
+
+        let current = [measurement(Duration::from_millis(1))];
+
+        assert!(baseline.regressions(&current, 15.0).is_empty());
+
+        let table = BenchTable {
+            measurements: &current,
+            baseline: Some(&baseline),
+        };
+
+        assert!(table.to_string().ends_with("      n/a"));
+    }
+}
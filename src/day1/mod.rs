@@ -22,14 +22,39 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 1 of Advent of Code 2021
+//! Solution for day 1 of Advent of Code 2021
 
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
 use anyhow::Context;
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
 use itertools::Itertools;
 
-static INPUT_DIR: LazyInputProvider = lazy_input!(1);
+pub const DAY: Day = Day {
+    number: 1,
+    inputs: lazy_input!(1),
+    embedded: embedded_input!(1, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<usize>;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_input(content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two(input).into())
+    }
+}
 
 fn parse_input(input: &str) -> anyhow::Result<Vec<usize>> {
     input
@@ -41,45 +66,20 @@ fn parse_input(input: &str) -> anyhow::Result<Vec<usize>> {
         .collect()
 }
 
-fn challenge_one(input: &str) -> anyhow::Result<usize> {
-    Ok(parse_input(input)?
-        .into_iter()
+fn challenge_one(input: &[usize]) -> usize {
+    input
+        .iter()
+        .copied()
         .tuple_windows::<(usize, usize)>()
-        .fold(0, |v, (a, b)| if b > a { v + 1 } else { v }))
+        .fold(0, |v, (a, b)| if b > a { v + 1 } else { v })
 }
 
-fn challenge_two(input: &str) -> anyhow::Result<usize> {
-    Ok(parse_input(input)?
-        .into_iter()
+fn challenge_two(input: &[usize]) -> usize {
+    input
+        .iter()
+        .copied()
         .tuple_windows::<(usize, usize, usize)>()
         .map(|(a, b, c)| a + b + c)
         .tuple_windows::<(usize, usize)>()
-        .fold(0, |v, (a, b)| if b > a { v + 1 } else { v }))
-}
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let content = INPUT_DIR
-        .get_input(&format!("{}.txt", name))
-        .context("reading content")?;
-
-    println!(
-        "Challenge one ({}): {}",
-        name,
-        challenge_one(&content).context("challenge one")?
-    );
-
-    println!(
-        "Challenge two ({}): {}",
-        name,
-        challenge_two(&content).context("challenge two")?
-    );
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
+        .fold(0, |v, (a, b)| if b > a { v + 1 } else { v })
 }
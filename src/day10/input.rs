@@ -22,7 +22,7 @@
  * SOFTWARE.
  */
 
-use aoc2021::nom::ParseResult;
+use crate::nom::ParseResult;
 use std::fmt::{Debug, Formatter, Write};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -59,6 +59,7 @@ pub struct Input {
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum LineResult {
     Ok,
     Incomplete {
@@ -103,7 +104,7 @@ impl Line {
 pub struct Parser;
 
 impl Parser {
-    fn parse_line(input: &str) -> ParseResult<Line> {
+    fn parse_line(input: &str) -> ParseResult<'_, Line> {
         nom::combinator::map(
             nom::multi::many0(nom::combinator::map(
                 nom::character::complete::one_of("()[]{}<>"),
@@ -123,7 +124,7 @@ impl Parser {
         )(input)
     }
 
-    pub fn parse_input(input: &str) -> ParseResult<Input> {
+    pub fn parse_input(input: &str) -> ParseResult<'_, Input> {
         nom::combinator::map(
             nom::multi::separated_list0(nom::character::complete::newline, Self::parse_line),
             |lines| Input { lines },
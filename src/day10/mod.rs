@@ -22,20 +22,43 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 10 of Advent of Code 2021
+//! Solution for day 10 of Advent of Code 2021
 
-use anyhow::Context;
 use itertools::Itertools;
 use tap::{Pipe, Tap};
 
-use aoc2021::{lazy_input, nom::parse_all, InputProvider, LazyInputProvider};
+use crate::{embedded_input, lazy_input, nom::parse_all, registry::Day, Answer, Solution};
 
-use crate::input::{Bracket, LineResult};
-
-static INPUT_DIR: LazyInputProvider = lazy_input!(10);
+use input::{Bracket, LineResult};
 
 mod input;
 
+pub const DAY: Day = Day {
+    number: 10,
+    inputs: lazy_input!(10),
+    embedded: embedded_input!(10, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = input::Input;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(input::Parser::parse_input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two(input).into())
+    }
+}
+
 fn challenge_one(input: &input::Input) -> usize {
     input
         .lines
@@ -80,26 +103,3 @@ fn challenge_two(input: &input::Input) -> usize {
         .tap_mut(|result| result.sort_unstable())
         .pipe(|result| result[(result.len() - 1) / 2])
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let data = parse_all(
-        input::Parser::parse_input,
-        INPUT_DIR
-            .get_input(&format!("{}.txt", name))
-            .context("reading content")?
-            .as_str(),
-    )?;
-
-    println!("Challenge one ({}): {}", name, challenge_one(&data));
-
-    println!("Challenge two ({}): {}", name, challenge_two(&data));
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
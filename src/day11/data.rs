@@ -22,70 +22,77 @@
  * SOFTWARE.
  */
 
-use arrayvec::ArrayVec;
+use crate::grid::{Grid, Position};
+use crate::viz::{Color, Frame, Glyph};
 use colored::Colorize;
-use itertools::Itertools;
 use std::fmt::{Debug, Formatter, Write};
 
 #[derive(Clone)]
 pub struct State {
-    energy: [[usize; 10]; 10],
-}
-
-fn iter_matrix() -> impl Iterator<Item = (usize, usize)> {
-    (0..10).cartesian_product(0..10)
+    energy: Grid<usize>,
 }
 
 impl State {
     const FLASHED: usize = 20;
 
-    pub fn advance_state(&mut self) -> usize {
-        // println!("Initial state:\n{:?}", self);
+    /// Number of octopuses in the grid
+    pub fn size(&self) -> usize {
+        self.energy.len()
+    }
 
-        // Advance all the energy levels by 1
-        for (i, j) in iter_matrix() {
-            self.energy[i][j] += 1;
+    /// Picture of the energy levels, highlighting the octopuses that just flashed
+    pub fn frame(&self, caption: String) -> Frame {
+        Frame {
+            caption,
+            cells: self.energy.map(|&level| {
+                let color = match level {
+                    0 => Color::White,
+                    9 => Color::Yellow,
+                    5..=8 => Color::Gray,
+                    _ => Color::DarkGray,
+                };
+
+                Glyph::new(char::from_digit(level as u32, 10).unwrap_or('*'), color)
+            }),
         }
+    }
 
-        // println!("Charged state:\n{:?}", self);
+    pub fn advance_state(&mut self) -> usize {
+        // Advance all the energy levels by 1
+        for level in self.energy.values_mut() {
+            *level += 1;
+        }
 
         // Flashes every energy level above 9
-        for (i, j) in iter_matrix() {
-            self.flash(i, j);
+        for position in self.energy.positions() {
+            self.flash(position);
         }
 
-        // println!("Flashing state:\n{:?}", self);
-
         let mut total_flashes = 0;
 
         // Reset all the energy levels that went above 9
-        for (i, j) in iter_matrix() {
-            if self.energy[i][j] > 9 {
-                self.energy[i][j] = 0;
+        for level in self.energy.values_mut() {
+            if *level > 9 {
+                *level = 0;
                 total_flashes += 1;
             }
         }
 
-        // println!("Cooldown state:\n{:?}", self);
-
         total_flashes
     }
 
-    fn flash(&mut self, x: usize, y: usize) {
-        let level = self.energy[x][y];
+    fn flash(&mut self, position: Position) {
+        let level = self.energy[position];
 
         if !(10..Self::FLASHED).contains(&level) {
             return;
         }
 
-        self.energy[x][y] = Self::FLASHED;
-
-        let neighbours = (x.saturating_sub(1)..=9.min(x + 1))
-            .cartesian_product(y.saturating_sub(1)..=9.min(y + 1));
+        self.energy[position] = Self::FLASHED;
 
-        for (i, j) in neighbours {
-            self.energy[i][j] = self.energy[i][j].saturating_add(1);
-            self.flash(i, j);
+        for neighbour in self.energy.neighbours8(position) {
+            self.energy[neighbour] = self.energy[neighbour].saturating_add(1);
+            self.flash(neighbour);
         }
     }
 }
@@ -94,31 +101,14 @@ pub struct Parser;
 
 impl Parser {
     pub fn parse_input(input: &str) -> anyhow::Result<State> {
-        input
-            .split('\n')
-            .map(|line| {
-                line.chars()
-                    .map(|c| c.to_digit(10).unwrap() as usize)
-                    .collect::<ArrayVec<_, 10>>()
-                    .into_inner()
-                    .map_err(|_| anyhow::anyhow!("incorrect number of elements on line"))
-                    .and_then(|v| {
-                        anyhow::ensure!(v.iter().all(|&x| x < 10), "invalid number");
-
-                        Ok(v)
-                    })
-            })
-            .collect::<Result<ArrayVec<_, 10>, _>>()?
-            .into_inner()
-            .map(|data| State { energy: data })
-            .map_err(|_| anyhow::anyhow!("incorrect number of lines"))
+        Grid::parse_digits(input).map(|energy| State { energy })
     }
 }
 
 impl Debug for State {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         // Print data as a matrix
-        for row in &self.energy {
+        for row in self.energy.rows() {
             for &col in row {
                 write!(
                     f,
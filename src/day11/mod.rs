@@ -22,17 +22,61 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 11 of Advent of Code 2021
-#![feature(backtrace)]
+//! Solution for day 11 of Advent of Code 2021
 
-use anyhow::Context;
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
-
-static INPUT_DIR: LazyInputProvider = lazy_input!(11);
+use crate::registry::Day;
+use crate::viz::Animation;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
 
 mod data;
 
+pub const DAY: Day = Day {
+    number: 11,
+    inputs: lazy_input!(11),
+    embedded: embedded_input!(11, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = data::State;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        data::Parser::parse_input(content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two(input).into())
+    }
+
+    /// Steps through the flashes until all the octopuses flash at once
+    fn animate(&self, input: &Self::Input) -> Option<Box<dyn Animation>> {
+        let initial = input.frame("initial energy levels".to_string());
+        let mut state = input.clone();
+        let mut synchronized = false;
+
+        let steps = (1..).map_while(move |step| {
+            if synchronized {
+                return None;
+            }
+
+            let flashes = state.advance_state();
+            synchronized = flashes == state.size();
+
+            Some(state.frame(format!("step {}: {} flashes", step, flashes)))
+        });
+
+        Some(Box::new(std::iter::once(initial).chain(steps)))
+    }
+}
+
 fn challenge_one(input: &data::State) -> usize {
     let mut input = input.clone();
 
@@ -49,33 +93,10 @@ fn challenge_two(input: &data::State) -> usize {
     let mut input = input.clone();
 
     for step in 1.. {
-        if input.advance_state() == 100 {
+        if input.advance_state() == input.size() {
             return step;
         }
     }
 
     unreachable!("For loop should break with the answer")
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let content = data::Parser::parse_input(
-        INPUT_DIR
-            .get_input(&format!("{}.txt", name))
-            .context("reading content")?
-            .as_str(),
-    )?;
-
-    println!("Challenge one ({}): {}", name, challenge_one(&content));
-
-    println!("Challenge two ({}): {}", name, challenge_two(&content));
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    colored::control::set_override(true);
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
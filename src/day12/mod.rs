@@ -22,17 +22,49 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 12 of Advent of Code 2021
+//! Solution for day 12 of Advent of Code 2021
 
-use crate::data::CaveSystem;
-use anyhow::{anyhow, Context};
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
-
-static INPUT_DIR: LazyInputProvider = lazy_input!(12);
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+use anyhow::anyhow;
+use data::CaveSystem;
 
 mod data;
 
+pub const DAY: Day = Day {
+    number: 12,
+    inputs: lazy_input!(12),
+    embedded: embedded_input!(
+        12,
+        "answers.toml",
+        "input.txt",
+        "sample-1.txt",
+        "sample-2.txt",
+        "sample-3.txt"
+    ),
+    default_inputs: &["sample-1", "sample-2", "sample-3", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = CaveSystem;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        data::Parser::parse_input(content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_one(input).map(Answer::from)
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_two(input).map(Answer::from)
+    }
+}
+
 fn challenge_one(input: &CaveSystem) -> anyhow::Result<usize> {
     Ok(input
         .get_cave("start")
@@ -48,35 +80,3 @@ fn challenge_two(input: &CaveSystem) -> anyhow::Result<usize> {
         .seek_single_double_small("end")
         .count())
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let content = data::Parser::parse_input(
-        INPUT_DIR
-            .get_input(&format!("{}.txt", name))
-            .context("reading content")?
-            .as_str(),
-    )?;
-
-    println!(
-        "Challenge one ({}): {}",
-        name,
-        challenge_one(&content).context("challenge one")?
-    );
-
-    println!(
-        "Challenge two ({}): {}",
-        name,
-        challenge_two(&content).context("challenge two")?
-    );
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample-1").context("sample data")?;
-    process("sample-2").context("sample data")?;
-    process("sample-3").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
@@ -24,6 +24,8 @@
 
 pub mod parser;
 
+use crate::image::{Image, BLACK, WHITE};
+use crate::viz::{Color, Frame, Glyph};
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter, Write};
 
@@ -72,6 +74,36 @@ pub struct Data {
     pub fold_instructions: Vec<FoldInstruction>,
 }
 
+impl Grid {
+    /// Image of the dots on the paper, each dot as a square of `scale` pixels
+    pub fn image(&self, scale: usize) -> Image {
+        let width = self.dots.iter().map(|p| p.0 + 1).max().unwrap_or(0);
+        let height = self.dots.iter().map(|p| p.1 + 1).max().unwrap_or(0);
+
+        let mut paper = crate::grid::Grid::filled(width, height, false);
+
+        for &Point(x, y) in &self.dots {
+            paper[(x, y)] = true;
+        }
+
+        Image::raster(&paper, scale, |&dot| if dot { WHITE } else { BLACK })
+    }
+
+    /// Picture of the dots on the paper
+    pub fn frame(&self, caption: String) -> Frame {
+        let width = self.dots.iter().map(|p| p.0 + 1).max().unwrap_or(0);
+        let height = self.dots.iter().map(|p| p.1 + 1).max().unwrap_or(0);
+
+        let mut cells = crate::grid::Grid::filled(width, height, Glyph::new('.', Color::DarkGray));
+
+        for &Point(x, y) in &self.dots {
+            cells[(x, y)] = Glyph::new('#', Color::Yellow);
+        }
+
+        Frame { caption, cells }
+    }
+}
+
 impl Debug for Grid {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let max_x = self.dots.iter().map(|p| p.0).max().unwrap();
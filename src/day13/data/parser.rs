@@ -25,27 +25,27 @@
 use nom::bytes::complete::tag;
 use nom::character::complete::{newline, one_of};
 use nom::combinator::map;
-use nom::multi::{count, separated_list1};
+use nom::multi::count;
 use nom::sequence::{preceded, separated_pair};
 
-use aoc2021::nom::{parse_usize, ParseResult};
+use crate::nom::{lines, parse_usize, ParseResult};
 
 use super::{Data, FoldInstruction, Grid, Point};
 
-fn parse_point(input: &str) -> ParseResult<Point> {
+fn parse_point(input: &str) -> ParseResult<'_, Point> {
     map(
         separated_pair(parse_usize, tag(","), parse_usize),
         |(x, y)| Point(x, y),
     )(input)
 }
 
-fn parse_grid(input: &str) -> ParseResult<Grid> {
-    map(separated_list1(newline, parse_point), |points| Grid {
+fn parse_grid(input: &str) -> ParseResult<'_, Grid> {
+    map(lines(parse_point), |points| Grid {
         dots: points.into_iter().collect(),
     })(input)
 }
 
-fn parse_instruction(input: &str) -> ParseResult<FoldInstruction> {
+fn parse_instruction(input: &str) -> ParseResult<'_, FoldInstruction> {
     map(
         preceded(
             tag("fold along "),
@@ -59,13 +59,9 @@ fn parse_instruction(input: &str) -> ParseResult<FoldInstruction> {
     )(input)
 }
 
-pub fn parse_input(input: &str) -> ParseResult<Data> {
+pub fn parse_input(input: &str) -> ParseResult<'_, Data> {
     map(
-        separated_pair(
-            parse_grid,
-            count(newline, 2),
-            separated_list1(newline, parse_instruction),
-        ),
+        separated_pair(parse_grid, count(newline, 2), lines(parse_instruction)),
         |(grid, instructions)| Data {
             grid,
             fold_instructions: instructions,
@@ -0,0 +1,105 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 13 of Advent of Code 2021
+
+use crate::image::Image;
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::viz::Animation;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+
+mod data;
+
+pub const DAY: Day = Day {
+    number: 13,
+    inputs: lazy_input!(13),
+    embedded: embedded_input!(13, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = data::Data;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(data::parser::parse_input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(format!("{:?}", challenge_two(input)).into())
+    }
+
+    /// Paper after every fold, showing the code
+    fn draw(&self, input: &Self::Input) -> Option<Image> {
+        Some(challenge_two(input).image(8))
+    }
+
+    /// Folds the paper one instruction at a time
+    fn animate(&self, input: &Self::Input) -> Option<Box<dyn Animation>> {
+        let initial = input.grid.frame(format!("{} dots", input.grid.dots.len()));
+        let instructions = input.fold_instructions.clone();
+        let total = instructions.len();
+
+        let folds = instructions.into_iter().enumerate().scan(
+            input.grid.clone(),
+            move |grid, (index, instruction)| {
+                *grid = instruction.apply(grid);
+
+                Some(grid.frame(format!(
+                    "fold {}/{} {:?}: {} dots",
+                    index + 1,
+                    total,
+                    instruction,
+                    grid.dots.len()
+                )))
+            },
+        );
+
+        Some(Box::new(std::iter::once(initial).chain(folds)))
+    }
+}
+
+fn challenge_one(input: &data::Data) -> usize {
+    let folded_grid: data::Grid = input.fold_instructions[0].apply(&input.grid);
+
+    folded_grid.dots.len()
+}
+
+fn challenge_two(input: &data::Data) -> data::Grid {
+    let mut grid: data::Grid = input.fold_instructions[0].apply(&input.grid);
+
+    for instruction in input.fold_instructions[1..].iter() {
+        grid = instruction.apply(&grid);
+    }
+
+    grid
+}
@@ -23,18 +23,18 @@
  */
 
 use super::{Data, Polymer, PolymerizationRules};
-use aoc2021::nom::ParseResult;
+use crate::nom::ParseResult;
 use nom::bytes::complete::tag;
 use nom::character::complete::{alpha1, anychar, newline};
 use nom::combinator::iterator;
 use nom::combinator::map;
 use nom::sequence::{pair, preceded, separated_pair};
 
-fn parse_polymer(input: &str) -> ParseResult<Polymer> {
+fn parse_polymer(input: &str) -> ParseResult<'_, Polymer> {
     map(alpha1, |c: &str| Polymer(c.chars().collect()))(input)
 }
 
-fn parse_polymerization_rules(input: &str) -> ParseResult<PolymerizationRules> {
+fn parse_polymerization_rules(input: &str) -> ParseResult<'_, PolymerizationRules> {
     let mut parse_iterator = iterator(
         input,
         preceded(
@@ -50,7 +50,7 @@ fn parse_polymerization_rules(input: &str) -> ParseResult<PolymerizationRules> {
     parse_iterator.finish().map(|(r, _)| (r, rules))
 }
 
-pub fn parse_input(input: &str) -> ParseResult<Data> {
+pub fn parse_input(input: &str) -> ParseResult<'_, Data> {
     let (rem, (polymer, rules)) =
         separated_pair(parse_polymer, newline, parse_polymerization_rules)(input)?;
 
@@ -22,27 +22,51 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 14 of Advent of Code 2021
+//! Solution for day 14 of Advent of Code 2021
 
-use anyhow::Context;
-use aoc2021::nom::parse_all;
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::ops::AddAssign;
 
-static INPUT_DIR: LazyInputProvider = lazy_input!(14);
-
 mod data;
 
+pub const DAY: Day = Day {
+    number: 14,
+    inputs: lazy_input!(14),
+    embedded: embedded_input!(14, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = data::Data;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(data::parser::parse_input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_one(input).map(Answer::from)
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_two(input).map(Answer::from)
+    }
+}
+
 fn extract_answer_from_counters(counters: HashMap<char, usize>) -> anyhow::Result<usize> {
     counters
         .into_iter()
         .minmax_by(|(_, left), (_, right)| left.cmp(right))
         .into_option()
         .map(|((min_c, min_v), (max_c, max_v))| {
-            println!("Min: {}  Max: {}", min_c, max_c);
+            log::debug!("Min: {}  Max: {}", min_c, max_c);
 
             max_v - min_v
         })
@@ -75,34 +99,3 @@ fn challenge_two(input: &data::Data) -> anyhow::Result<usize> {
 
     extract_answer_from_counters(element_counters)
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let data = parse_all(
-        data::parser::parse_input,
-        INPUT_DIR
-            .get_input(&format!("{}.txt", name))
-            .context("reading content")?
-            .as_str(),
-    )?;
-
-    println!(
-        "Challenge one ({}): {}",
-        name,
-        challenge_one(&data).context("challenge one")?
-    );
-
-    println!(
-        "Challenge two ({}): {}",
-        name,
-        challenge_two(&data).context("challenge two")?
-    );
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
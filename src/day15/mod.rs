@@ -0,0 +1,124 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 15 of Advent of Code 2021
+
+use crate::grid::Grid;
+use crate::registry::Day;
+use crate::search::{astar, Path};
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+
+pub const DAY: Day = Day {
+    number: 15,
+    inputs: lazy_input!(15),
+    embedded: embedded_input!(15, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Grid<usize>;
+
+    /// Parses the risk levels, which range from 1 to 9
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        let grid = Grid::parse_digits(content)?;
+
+        if let Some(((x, y), _)) = grid.iter().find(|(_, &risk)| risk == 0) {
+            anyhow::bail!("risk level at ({}, {}) is 0, expected 1 to 9", x, y);
+        }
+
+        Ok(grid)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_one(input).map(Answer::from)
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_two(input).map(Answer::from)
+    }
+}
+
+/// Finds the path with the lowest total risk from the top left to the bottom right of the grid,
+/// if the grid has any cell
+fn safest_path(grid: &Grid<usize>) -> Option<Path<(usize, usize), usize>> {
+    let goal = (grid.width().checked_sub(1)?, grid.height().checked_sub(1)?);
+
+    // Parsing rejects risk levels of 0, so entering any cell costs at least 1 and the manhattan
+    // distance never overestimates
+    astar(
+        (0, 0),
+        |&position| {
+            grid.neighbours4(position)
+                .map(|neighbour| (neighbour, grid[neighbour]))
+        },
+        |&(x, y)| (goal.0 - x) + (goal.1 - y),
+        |&position| position == goal,
+    )
+}
+
+fn challenge_one(input: &Grid<usize>) -> anyhow::Result<usize> {
+    safest_path(input)
+        .map(|path| path.cost)
+        .ok_or_else(|| anyhow::anyhow!("No path found"))
+}
+
+fn challenge_two(input: &Grid<usize>) -> anyhow::Result<usize> {
+    let (width, height) = (input.width(), input.height());
+    let mut expanded_grid = Grid::new(width * 5, height * 5);
+
+    for (x, y) in expanded_grid.positions() {
+        let offset = (x / width) + (y / height);
+
+        expanded_grid[(x, y)] = (input[(x % width, y % height)] + offset - 1) % 9 + 1;
+    }
+
+    challenge_one(&expanded_grid)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_grid() {
+        let grid = Grid::new(0, 0);
+
+        assert!(safest_path(&grid).is_none());
+        assert!(challenge_two(&grid).is_err());
+    }
+
+    #[test]
+    fn zero_risk() {
+        let error = Solver.parse("19\n10").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "risk level at (1, 1) is 0, expected 1 to 9"
+        );
+    }
+}
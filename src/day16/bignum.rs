@@ -0,0 +1,215 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Minimal arbitrary-precision unsigned integers, enough to evaluate any BITS expression
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// Unsigned integer of any size, stored as base 2^32 limbs from the least significant
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct BigUint {
+    /// Never ends with a zero limb, so zero has no limbs at all
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    fn normalized(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+
+        Self { limbs }
+    }
+
+    /// Value as a u64, if it fits in one
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(u64::from(low)),
+            [low, high] => Some(u64::from(high) << 32 | u64::from(low)),
+            _ => None,
+        }
+    }
+
+    /// Divides in place by a small divisor, returning the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0_u64;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let current = remainder << 32 | u64::from(*limb);
+            // The quotient of each step fits in a limb as the remainder is lower than the divisor
+            *limb = (current / u64::from(divisor)) as u32;
+            remainder = current % u64::from(divisor);
+        }
+
+        *self = Self::normalized(std::mem::take(&mut self.limbs));
+
+        remainder as u32
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::normalized(vec![value as u32, (value >> 32) as u32])
+    }
+}
+
+impl std::ops::Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let length = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(length + 1);
+        let mut carry = 0_u64;
+
+        for index in 0..length {
+            let a = self.limbs.get(index).copied().unwrap_or(0);
+            let b = other.limbs.get(index).copied().unwrap_or(0);
+            let sum = u64::from(a) + u64::from(b) + carry;
+
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+
+        limbs.push(carry as u32);
+
+        BigUint::normalized(limbs)
+    }
+}
+
+impl std::ops::Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0_u32; self.limbs.len() + other.limbs.len()];
+
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0_u64;
+
+            for (j, &b) in other.limbs.iter().enumerate() {
+                // Cannot overflow: (2^32 - 1)^2 + 2 * (2^32 - 1) = 2^64 - 1
+                let product = u64::from(a) * u64::from(b) + u64::from(limbs[i + j]) + carry;
+
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint::normalized(limbs)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+
+        while !rest.limbs.is_empty() {
+            chunks.push(rest.div_rem_small(CHUNK));
+        }
+
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((most_significant, others)) => {
+                write!(f, "{}", most_significant)?;
+
+                for chunk in others.iter().rev() {
+                    write!(f, "{:09}", chunk)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0 ; "zero")]
+    #[test_case(1_000_000_000 ; "one chunk")]
+    #[test_case(u64::MAX ; "max")]
+    fn round_trip(value: u64) {
+        let big = BigUint::from(value);
+
+        assert_eq!(big.to_u64(), Some(value));
+        assert_eq!(big.to_string(), value.to_string());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let max = BigUint::from(u64::MAX);
+        let square = &max * &max;
+
+        assert_eq!(
+            square.to_string(),
+            (u128::from(u64::MAX).pow(2)).to_string()
+        );
+        assert_eq!(
+            (&max + &BigUint::from(1)).to_string(),
+            "18446744073709551616"
+        );
+        assert_eq!(square.to_u64(), None);
+        assert_eq!((&square * &BigUint::default()).to_u64(), Some(0));
+    }
+
+    #[test]
+    fn factorial() {
+        let factorial = (1..=30).fold(BigUint::from(1), |acc, n| &acc * &BigUint::from(n));
+
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+    }
+
+    #[test]
+    fn ordering() {
+        let small = BigUint::from(u64::MAX);
+        let large = &small + &small;
+
+        assert!(small < large);
+        assert!(BigUint::from(2) > BigUint::from(1));
+        assert_eq!(large.cmp(&large.clone()), Ordering::Equal);
+    }
+}
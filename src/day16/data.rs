@@ -24,7 +24,10 @@
 
 use num_enum::TryFromPrimitive;
 
-#[derive(Debug, Eq, PartialEq)]
+/// Type ID of literal value packets, operators use the ID of their [`OperatorType`]
+pub const LITERAL_TYPE: u8 = 4;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Packet {
     Literal {
         version: u8,
@@ -56,66 +59,63 @@ impl Packet {
         }
     }
 
+    /// Lists the packet and all its sub-packets, in the order they appear in the transmission
     pub fn linearize(&self) -> Vec<&Packet> {
-        let mut result = vec![self];
+        let mut result = Vec::new();
+        let mut pending = vec![self];
+
+        while let Some(packet) = pending.pop() {
+            result.push(packet);
 
-        if let Packet::Operator { packets, .. } = self {
-            for packet in packets {
-                result.extend(packet.linearize());
+            if let Packet::Operator { packets, .. } = packet {
+                pending.extend(packets.iter().rev());
             }
         }
 
         result
     }
+}
+
+impl Drop for Packet {
+    /// Drops the sub-packets from a flat list, as dropping each one inside its operator
+    /// recurses as deep as the packets are nested
+    fn drop(&mut self) {
+        let mut pending = match self {
+            Packet::Literal { .. } => return,
+            Packet::Operator { packets, .. } => std::mem::take(packets),
+        };
 
-    pub fn evaluate(&self) -> u64 {
-        match *self {
-            Packet::Literal { value, .. } => value,
-            Packet::Operator {
-                operator_type,
-                ref packets,
-                ..
-            } => operator_type.apply(packets),
+        while let Some(mut packet) = pending.pop() {
+            if let Packet::Operator { packets, .. } = &mut packet {
+                pending.append(packets);
+            }
         }
     }
 }
 
 impl OperatorType {
-    fn apply(self, packets: &[Packet]) -> u64 {
+    /// Name of the operation, as a function
+    pub fn name(self) -> &'static str {
         match self {
-            OperatorType::Sum => packets.iter().map(Packet::evaluate).sum(),
-            OperatorType::Product => packets.iter().map(Packet::evaluate).product(),
-            OperatorType::Minimum => packets
-                .iter()
-                .map(Packet::evaluate)
-                .min()
-                .expect("there must be at least one packet"),
-            OperatorType::Maximum => packets
-                .iter()
-                .map(Packet::evaluate)
-                .max()
-                .expect("there must be at least one packet"),
-            OperatorType::GreaterThan => {
-                assert_eq!(packets.len(), 2);
-                let a = packets[0].evaluate();
-                let b = packets[1].evaluate();
-
-                u64::from(a > b)
-            }
-            OperatorType::LessThan => {
-                assert_eq!(packets.len(), 2);
-                let a = packets[0].evaluate();
-                let b = packets[1].evaluate();
-
-                u64::from(a < b)
-            }
-            OperatorType::EqualTo => {
-                assert_eq!(packets.len(), 2);
-                let a = packets[0].evaluate();
-                let b = packets[1].evaluate();
+            OperatorType::Sum => "sum",
+            OperatorType::Product => "product",
+            OperatorType::Minimum => "min",
+            OperatorType::Maximum => "max",
+            OperatorType::GreaterThan => "gt",
+            OperatorType::LessThan => "lt",
+            OperatorType::EqualTo => "eq",
+        }
+    }
 
-                u64::from(a == b)
-            }
+    /// Symbol of the operation, if it is usually written as an infix operator
+    pub fn symbol(self) -> Option<&'static str> {
+        match self {
+            OperatorType::Sum => Some("+"),
+            OperatorType::Product => Some("*"),
+            OperatorType::Minimum | OperatorType::Maximum => None,
+            OperatorType::GreaterThan => Some(">"),
+            OperatorType::LessThan => Some("<"),
+            OperatorType::EqualTo => Some("=="),
         }
     }
 }
@@ -0,0 +1,286 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Listing of the packets of a BITS transmission along with the bits they are made of
+
+use super::data::{OperatorType, LITERAL_TYPE};
+use super::encoder::LengthType;
+use super::stream::{Decoder, Event};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::ops::Range;
+
+/// What a packet holds after its header
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Content {
+    Literal(u64),
+    Operator {
+        length_type: LengthType,
+        length: usize,
+    },
+}
+
+/// Deepest nesting shown by indenting a [`Line`], deeper packets being aligned with it so the
+/// listing of a deeply nested transmission does not grow quadratically
+pub const MAX_INDENT: usize = 32;
+
+/// A single packet of a transmission, without its sub-packets
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Line {
+    /// Position of the first bit of the packet in the transmission
+    pub offset: usize,
+    /// Number of operators the packet is nested in
+    pub depth: usize,
+    pub version: u8,
+    pub type_id: u8,
+    pub content: Content,
+    /// Raw bits of the packet up to its sub-packets, split in fields by spaces
+    pub bits: String,
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mnemonic = if self.type_id == LITERAL_TYPE {
+            "lit"
+        } else {
+            OperatorType::try_from(self.type_id).map_or("???", OperatorType::name)
+        };
+
+        let detail = match self.content {
+            Content::Literal(value) => value.to_string(),
+            Content::Operator {
+                length_type: LengthType::Bits,
+                length,
+            } => format!("bits={}", length),
+            Content::Operator {
+                length_type: LengthType::Packets,
+                length,
+            } => format!("packets={}", length),
+        };
+
+        write!(
+            f,
+            "{:>6}  {:indent$}v{} {} {:<7} {:<16} {}",
+            self.offset,
+            "",
+            self.version,
+            self.type_id,
+            mnemonic,
+            detail,
+            self.bits,
+            indent = self.depth.min(MAX_INDENT) * 2
+        )
+    }
+}
+
+/// Turns decoded events into lines, rebuilding the bits of each packet from its fields
+#[derive(Debug, Default)]
+struct Disassembler {
+    /// Number of operators open at the current event
+    depth: usize,
+}
+
+impl Disassembler {
+    /// Line of the packet started by an event, if it starts one
+    fn line(&mut self, event: Event, bits: Range<usize>) -> Option<Line> {
+        let field = |value: u64, width: usize| format!("{:0width$b}", value, width = width);
+
+        match event {
+            Event::Literal { version, value } => {
+                let mut fields = vec![field(version.into(), 3), field(LITERAL_TYPE.into(), 3)];
+
+                // Groups beyond the 16th can only be leading zeros
+                for group in (0..(bits.len() - 6) / 5).rev() {
+                    let nibble = if group < 16 {
+                        value >> (group * 4) & 0xF
+                    } else {
+                        0
+                    };
+                    fields.push(format!("{}{}", u8::from(group > 0), field(nibble, 4)));
+                }
+
+                Some(Line {
+                    offset: bits.start,
+                    depth: self.depth,
+                    version,
+                    type_id: LITERAL_TYPE,
+                    content: Content::Literal(value),
+                    bits: fields.join(" "),
+                })
+            }
+            Event::Begin {
+                version,
+                operator_type,
+                length_type,
+                length,
+            } => {
+                self.depth += 1;
+
+                let fields = [
+                    field(version.into(), 3),
+                    field(operator_type as u64, 3),
+                    field(length_type as u64, 1),
+                    field(length as u64, length_type.width()),
+                ];
+
+                Some(Line {
+                    offset: bits.start,
+                    depth: self.depth - 1,
+                    version,
+                    type_id: operator_type as u8,
+                    content: Content::Operator {
+                        length_type,
+                        length,
+                    },
+                    bits: fields.join(" "),
+                })
+            }
+            Event::End => {
+                self.depth -= 1;
+                None
+            }
+        }
+    }
+}
+
+/// Lists every packet of the hexadecimal transmissions of a reader in the order they appear,
+/// each line as soon as its packet is decoded. Offsets restart at each transmission.
+///
+/// # Errors
+/// An error is returned by the iterator if the reader fails or a transmission is not valid, see
+/// [`Decoder::next_event`]
+pub fn disassemble<R: Read>(reader: R) -> impl Iterator<Item = anyhow::Result<Line>> {
+    let mut decoder = Decoder::new(reader);
+    let mut disassembler = Disassembler::default();
+
+    std::iter::from_fn(move || loop {
+        match decoder.next_spanned_event() {
+            Err(error) => return Some(Err(error)),
+            Ok(None) => return None,
+            Ok(Some((event, bits))) => {
+                if let Some(line) = disassembler.line(event, bits) {
+                    return Some(Ok(line));
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn literal() {
+        let lines = disassemble("D2FE28".as_bytes())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(
+            lines,
+            vec![Line {
+                offset: 0,
+                depth: 0,
+                version: 6,
+                type_id: 4,
+                content: Content::Literal(2021),
+                bits: "110 100 10111 11110 00101".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn leading_zero_groups() {
+        let lines = disassemble("D205".as_bytes())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(lines[0].content, Content::Literal(5));
+        assert_eq!(lines[0].bits, "110 100 10000 00101");
+    }
+
+    #[test]
+    fn operator_bit_length() {
+        let lines = disassemble("38006F45291200".as_bytes())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(
+            lines
+                .iter()
+                .map(|line| (line.offset, line.depth, line.bits.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, 0, "001 110 0 000000000011011"),
+                (22, 1, "110 100 01010"),
+                (33, 1, "010 100 10001 00100"),
+            ]
+        );
+        assert_eq!(
+            lines[0].content,
+            Content::Operator {
+                length_type: LengthType::Bits,
+                length: 27
+            }
+        );
+    }
+
+    #[test]
+    fn display() {
+        let lines = disassemble("EE00D40C823060".as_bytes())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(
+            lines[0].to_string(),
+            "     0  v7 3 max     packets=3        111 011 1 00000000011"
+        );
+        assert_eq!(
+            lines[1].to_string(),
+            "    18    v2 4 lit     1                010 100 00001"
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        assert!(disassemble("38006F452912".as_bytes()).any(|line| line.is_err()));
+    }
+
+    #[test]
+    fn indent_limit() {
+        let line = |depth| Line {
+            offset: 0,
+            depth,
+            version: 0,
+            type_id: LITERAL_TYPE,
+            content: Content::Literal(1),
+            bits: String::new(),
+        };
+
+        assert_eq!(
+            line(MAX_INDENT + 100).to_string(),
+            line(MAX_INDENT).to_string()
+        );
+    }
+}
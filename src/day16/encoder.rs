@@ -0,0 +1,287 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Serialization of packets back into BITS transmissions
+
+use super::data::{Packet, LITERAL_TYPE};
+
+/// Largest sub-packet length, in bits, of an operator with length type 0
+const MAX_BIT_LENGTH: usize = (1 << 15) - 1;
+
+/// Largest number of sub-packets of an operator with length type 1
+const MAX_PACKET_COUNT: usize = (1 << 11) - 1;
+
+/// How an operator announces the length of its sub-packets
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LengthType {
+    /// Length type 0, the total number of bits of the sub-packets
+    Bits = 0,
+    /// Length type 1, the number of sub-packets
+    Packets = 1,
+}
+
+impl LengthType {
+    /// Number of bits of the length following the length type
+    pub fn width(self) -> usize {
+        match self {
+            LengthType::Bits => 15,
+            LengthType::Packets => 11,
+        }
+    }
+}
+
+/// Sequence of bits, written from the most significant bit of each byte
+#[derive(Debug, Default, Clone)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    fn push_bit(&mut self, bit: bool) {
+        if self.len.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+
+        if bit {
+            self.bytes[self.len / 8] |= 0x80 >> (self.len % 8);
+        }
+
+        self.len += 1;
+    }
+
+    /// Writes the lowest `width` bits of the value, most significant first
+    fn push(&mut self, value: u64, width: usize) {
+        for shift in (0..width).rev() {
+            self.push_bit((value >> shift) & 1 == 1);
+        }
+    }
+}
+
+/// Encodes packets, choosing the length type of each operator
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Encoder {
+    /// Length type used for every operator when it can hold its sub-packets.
+    /// Without one the shortest encoding is used.
+    pub preferred: Option<LengthType>,
+}
+
+impl Encoder {
+    /// Encodes a packet into bytes, padding the last one with zeros
+    ///
+    /// The tree is walked with explicit stacks: a first pass measures every operator from its
+    /// last packet back to the root to choose its length type, then the packets are written in
+    /// order.
+    ///
+    /// # Errors
+    /// An error is returned if a version does not fit in 3 bits or if an operator has too many
+    /// sub-packets to be encoded with either length type.
+    pub fn encode(&self, packet: &Packet) -> anyhow::Result<Vec<u8>> {
+        // Packets in the order they are written, along with the position of their operator
+        let mut packets = Vec::new();
+        let mut pending = vec![(packet, None)];
+
+        while let Some((packet, parent)) = pending.pop() {
+            let version = packet.version();
+            anyhow::ensure!(version < 8, "version {} does not fit in 3 bits", version);
+
+            if let Packet::Operator { packets: sub, .. } = packet {
+                let position = Some(packets.len());
+                pending.extend(sub.iter().rev().map(|packet| (packet, position)));
+            }
+
+            packets.push((packet, parent));
+        }
+
+        // Number of bits of the sub-packets of each operator
+        let mut contents = vec![0; packets.len()];
+        let mut length_types = vec![LengthType::Packets; packets.len()];
+
+        for (position, &(packet, parent)) in packets.iter().enumerate().rev() {
+            let length = match packet {
+                Packet::Literal { value, .. } => 6 + 5 * literal_groups(*value),
+                Packet::Operator { packets, .. } => {
+                    let length_type = self.length_type(packets.len(), contents[position])?;
+                    length_types[position] = length_type;
+
+                    7 + length_type.width() + contents[position]
+                }
+            };
+
+            if let Some(parent) = parent {
+                contents[parent] += length;
+            }
+        }
+
+        let mut bits = BitWriter::default();
+
+        for (position, &(packet, _)) in packets.iter().enumerate() {
+            bits.push(u64::from(packet.version()), 3);
+
+            match packet {
+                Packet::Literal { value, .. } => {
+                    bits.push(u64::from(LITERAL_TYPE), 3);
+                    write_literal(*value, &mut bits);
+                }
+                Packet::Operator {
+                    operator_type,
+                    packets,
+                    ..
+                } => {
+                    bits.push(*operator_type as u64, 3);
+
+                    let length_type = length_types[position];
+                    let length = match length_type {
+                        LengthType::Bits => contents[position],
+                        LengthType::Packets => packets.len(),
+                    };
+
+                    bits.push(length_type as u64, 1);
+                    bits.push(length as u64, length_type.width());
+                }
+            }
+        }
+
+        Ok(bits.bytes)
+    }
+
+    /// Encodes a packet into the uppercase hexadecimal form of a transmission
+    ///
+    /// # Errors
+    /// An error is returned if the packet cannot be encoded, see [`Encoder::encode`]
+    pub fn encode_hex(&self, packet: &Packet) -> anyhow::Result<String> {
+        self.encode(packet).map(hex::encode_upper)
+    }
+
+    /// Length type for an operator, falling back to the other one when the preferred one cannot
+    /// hold the sub-packets
+    fn length_type(&self, count: usize, bit_length: usize) -> anyhow::Result<LengthType> {
+        let fits = |length_type: &LengthType| match length_type {
+            LengthType::Bits => bit_length <= MAX_BIT_LENGTH,
+            LengthType::Packets => count <= MAX_PACKET_COUNT,
+        };
+
+        // Counting packets takes 4 bits less than counting bits
+        let order = match self.preferred {
+            Some(LengthType::Bits) => [LengthType::Bits, LengthType::Packets],
+            _ => [LengthType::Packets, LengthType::Bits],
+        };
+
+        order.into_iter().find(fits).ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} sub-packets spanning {} bits do not fit in any length type",
+                count,
+                bit_length
+            )
+        })
+    }
+}
+
+/// Number of 4 bit groups needed to write a literal value
+fn literal_groups(value: u64) -> usize {
+    let significant_bits = (u64::BITS - value.leading_zeros()) as usize;
+    significant_bits.div_ceil(4).max(1)
+}
+
+/// Writes a literal value as groups of 4 bits, each preceded by whether more groups follow
+fn write_literal(value: u64, bits: &mut BitWriter) {
+    for group in (0..literal_groups(value)).rev() {
+        bits.push(u64::from(group > 0), 1);
+        bits.push(value >> (group * 4), 4);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day16::data::OperatorType;
+    use crate::day16::parser::parse_packet_bits;
+    use crate::day16::strategy::packet;
+    use crate::nom::parse_all_bits;
+    use proptest::prelude::*;
+    use test_case::test_case;
+
+    fn decode(hex: &str) -> Packet {
+        parse_all_bits(parse_packet_bits, &hex::decode(hex).unwrap()).unwrap()
+    }
+
+    #[test_case("D2FE28", None ; "literal")]
+    #[test_case("38006F45291200", Some(LengthType::Bits) ; "bit length")]
+    #[test_case("EE00D40C823060", None ; "packet count")]
+    fn reencode(transmission: &str, preferred: Option<LengthType>) {
+        let encoder = Encoder { preferred };
+
+        assert_eq!(
+            encoder.encode_hex(&decode(transmission)).unwrap(),
+            transmission
+        );
+    }
+
+    #[test]
+    fn too_many_packets_for_count() {
+        let packet = Packet::Operator {
+            version: 0,
+            operator_type: OperatorType::Sum,
+            packets: (0..=MAX_PACKET_COUNT as u64)
+                .map(|value| Packet::Literal {
+                    version: 0,
+                    value: value % 16,
+                })
+                .collect(),
+        };
+
+        // Each literal takes 11 bits, which still fits in a bit length
+        let bytes = Encoder::default().encode(&packet).unwrap();
+
+        assert_eq!(bytes[0] & 0b0000_0010, 0, "length type 0 expected");
+        assert_eq!(parse_all_bits(parse_packet_bits, &bytes).unwrap(), packet);
+    }
+
+    #[test]
+    fn invalid_version() {
+        let packet = Packet::Literal {
+            version: 8,
+            value: 0,
+        };
+
+        assert!(Encoder::default().encode(&packet).is_err());
+    }
+
+    fn preferred() -> impl Strategy<Value = Option<LengthType>> {
+        prop_oneof![
+            Just(None),
+            Just(Some(LengthType::Bits)),
+            Just(Some(LengthType::Packets)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(packet in packet(), preferred in preferred()) {
+            let bytes = Encoder { preferred }.encode(&packet).unwrap();
+
+            prop_assert_eq!(parse_all_bits(parse_packet_bits, &bytes).unwrap(), packet);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Checked evaluation of the expression held by a packet
+
+use super::bignum::BigUint;
+use super::data::{OperatorType, Packet};
+use super::vm::Program;
+use std::fmt::{Display, Formatter};
+
+/// Position of a packet in the tree, as the indices of the sub-packets leading to it from the root
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct PacketPath(pub Vec<usize>);
+
+impl Display for PacketPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "/");
+        }
+
+        for index in &self.0 {
+            write!(f, "/{}", index)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reason a packet cannot be evaluated
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum EvaluationError {
+    #[error("`{}` packet at {path} has {operands} operands, expected 2", .operator_type.name())]
+    Arity {
+        path: PacketPath,
+        operator_type: OperatorType,
+        operands: usize,
+    },
+    #[error("`{}` packet at {path} has no operands", .operator_type.name())]
+    Empty {
+        path: PacketPath,
+        operator_type: OperatorType,
+    },
+    #[error("`{}` packet at {path} overflows", .operator_type.name())]
+    Overflow {
+        path: PacketPath,
+        operator_type: OperatorType,
+    },
+}
+
+/// Number a packet can be evaluated to
+pub trait Value: Ord + From<u64> {
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+}
+
+impl Value for u64 {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u64::checked_add(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        u64::checked_mul(*self, *other)
+    }
+}
+
+impl Value for BigUint {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+impl Packet {
+    /// Evaluates the expression of the packet, compiling it to a [`Program`] so deeply nested
+    /// packets do not recurse
+    ///
+    /// # Errors
+    /// An error is returned if an operator has the wrong number of operands or a result does not
+    /// fit in a u64
+    pub fn evaluate(&self) -> Result<u64, EvaluationError> {
+        Program::compile(self).evaluate()
+    }
+
+    /// Evaluates the expression of the packet with arbitrary precision, so it never overflows
+    ///
+    /// # Errors
+    /// An error is returned if an operator has the wrong number of operands
+    pub fn evaluate_big(&self) -> Result<BigUint, EvaluationError> {
+        Program::compile(self).evaluate()
+    }
+
+    /// Evaluates the expression by recursing into the tree, as an oracle for the compiled program
+    #[cfg(test)]
+    pub(super) fn evaluate_tree<V: Value>(&self) -> Result<V, EvaluationError> {
+        self.evaluate_as(&mut Vec::new())
+    }
+
+    #[cfg(test)]
+    fn evaluate_as<V: Value>(&self, path: &mut Vec<usize>) -> Result<V, EvaluationError> {
+        let (operator_type, packets) = match self {
+            Packet::Literal { value, .. } => return Ok(V::from(*value)),
+            Packet::Operator {
+                operator_type,
+                packets,
+                ..
+            } => (*operator_type, packets),
+        };
+
+        let mut operands = Vec::with_capacity(packets.len());
+
+        for (index, packet) in packets.iter().enumerate() {
+            path.push(index);
+            operands.push(packet.evaluate_as::<V>(path)?);
+            path.pop();
+        }
+
+        apply(operator_type, operands, || PacketPath(path.clone()))
+    }
+}
+
+/// Applies an operator to its evaluated operands, `path` locating the operator in case of error
+pub(super) fn apply<V: Value>(
+    operator_type: OperatorType,
+    operands: Vec<V>,
+    path: impl Fn() -> PacketPath,
+) -> Result<V, EvaluationError> {
+    let count = operands.len();
+    let mut operands = operands.into_iter();
+
+    let first = operands.next().ok_or_else(|| EvaluationError::Empty {
+        path: path(),
+        operator_type,
+    })?;
+
+    let overflow = || EvaluationError::Overflow {
+        path: path(),
+        operator_type,
+    };
+
+    match operator_type {
+        OperatorType::Sum => operands
+            .try_fold(first, |total, operand| total.checked_add(&operand))
+            .ok_or_else(overflow),
+        OperatorType::Product => operands
+            .try_fold(first, |total, operand| total.checked_mul(&operand))
+            .ok_or_else(overflow),
+        OperatorType::Minimum => Ok(operands.fold(first, V::min)),
+        OperatorType::Maximum => Ok(operands.fold(first, V::max)),
+        OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo => {
+            let (Some(second), None) = (operands.next(), operands.next()) else {
+                return Err(EvaluationError::Arity {
+                    path: path(),
+                    operator_type,
+                    operands: count,
+                });
+            };
+
+            let result = match operator_type {
+                OperatorType::GreaterThan => first > second,
+                OperatorType::LessThan => first < second,
+                _ => first == second,
+            };
+
+            Ok(V::from(u64::from(result)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day16::strategy::{literal, operator, packet};
+    use proptest::prelude::*;
+
+    #[test]
+    fn arity() {
+        let packet = operator(
+            OperatorType::Sum,
+            vec![
+                literal(1),
+                operator(
+                    OperatorType::LessThan,
+                    vec![literal(1), literal(2), literal(3)],
+                ),
+            ],
+        );
+
+        let error = packet.evaluate().unwrap_err();
+
+        assert_eq!(
+            error,
+            EvaluationError::Arity {
+                path: PacketPath(vec![1]),
+                operator_type: OperatorType::LessThan,
+                operands: 3
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "`lt` packet at /1 has 3 operands, expected 2"
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let packet = operator(OperatorType::Maximum, vec![]);
+
+        assert_eq!(
+            packet.evaluate_big(),
+            Err(EvaluationError::Empty {
+                path: PacketPath::default(),
+                operator_type: OperatorType::Maximum
+            })
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let packet = operator(
+            OperatorType::Minimum,
+            vec![
+                literal(7),
+                operator(OperatorType::Product, vec![literal(u64::MAX), literal(2)]),
+            ],
+        );
+
+        assert_eq!(
+            packet.evaluate(),
+            Err(EvaluationError::Overflow {
+                path: PacketPath(vec![1]),
+                operator_type: OperatorType::Product
+            })
+        );
+        assert_eq!(packet.evaluate_big(), Ok(BigUint::from(7)));
+    }
+
+    #[test]
+    fn deep_nesting() {
+        let packet = (0..100_000).fold(literal(1), |packet, _| {
+            operator(OperatorType::Product, vec![packet])
+        });
+
+        assert_eq!(packet.evaluate(), Ok(1));
+        assert_eq!(packet.evaluate_big(), Ok(BigUint::from(1)));
+    }
+
+    proptest! {
+        #[test]
+        fn big_agrees(packet in packet()) {
+            match packet.evaluate() {
+                Ok(value) => prop_assert_eq!(packet.evaluate_big(), Ok(BigUint::from(value))),
+                // Operands after the overflow may still be invalid
+                Err(EvaluationError::Overflow { .. }) => {
+                    let overflows = matches!(packet.evaluate_big(), Err(EvaluationError::Overflow { .. }));
+                    prop_assert!(!overflows);
+                }
+                Err(error) => prop_assert_eq!(packet.evaluate_big(), Err(error)),
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Rendering of packets as the expressions they evaluate
+
+use super::data::{OperatorType, Packet};
+use std::fmt::{Display, Formatter};
+
+/// Packet written with infix operators where possible, e.g. `max(3 + 4, 2 * 5)`
+pub struct Infix<'a>(pub &'a Packet);
+
+/// Packet written as an S-expression, e.g. `(max (+ 3 4) (* 2 5))`
+pub struct SExpr<'a>(pub &'a Packet);
+
+impl Packet {
+    pub fn infix(&self) -> Infix<'_> {
+        Infix(self)
+    }
+
+    pub fn sexpr(&self) -> SExpr<'_> {
+        SExpr(self)
+    }
+
+    /// Whether the packet is rendered with an infix operator, and so needs parentheses as an operand.
+    ///
+    /// Comparisons are only infix with exactly two operands, so one with the wrong arity stands out.
+    fn is_infix(&self) -> bool {
+        match self {
+            Packet::Literal { .. } => false,
+            Packet::Operator {
+                operator_type,
+                packets,
+                ..
+            } => match operator_type {
+                OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo => {
+                    packets.len() == 2
+                }
+                _ => operator_type.symbol().is_some() && packets.len() >= 2,
+            },
+        }
+    }
+}
+
+/// What is left to write of an expression, kept on a stack so deep packets do not recurse
+enum Piece<'a> {
+    Packet(&'a Packet),
+    Text(&'static str),
+}
+
+/// Writes a packet, `expand` pushing on the stack the pieces an operator is written as, in
+/// reverse order
+fn render<'a, F>(f: &mut Formatter<'_>, packet: &'a Packet, mut expand: F) -> std::fmt::Result
+where
+    F: FnMut(&mut Vec<Piece<'a>>, &'a Packet, OperatorType, &'a [Packet]),
+{
+    let mut pending = vec![Piece::Packet(packet)];
+
+    while let Some(piece) = pending.pop() {
+        match piece {
+            Piece::Text(text) => f.write_str(text)?,
+            Piece::Packet(Packet::Literal { value, .. }) => write!(f, "{}", value)?,
+            Piece::Packet(
+                packet @ Packet::Operator {
+                    operator_type,
+                    packets,
+                    ..
+                },
+            ) => expand(&mut pending, packet, *operator_type, packets),
+        }
+    }
+
+    Ok(())
+}
+
+impl Display for Infix<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        render(
+            f,
+            self.0,
+            |pending, packet, operator_type, packets| match operator_type.symbol() {
+                Some(symbol) if packet.is_infix() => {
+                    for (index, packet) in packets.iter().enumerate().rev() {
+                        if packet.is_infix() {
+                            pending.extend([
+                                Piece::Text(")"),
+                                Piece::Packet(packet),
+                                Piece::Text("("),
+                            ]);
+                        } else {
+                            pending.push(Piece::Packet(packet));
+                        }
+
+                        if index > 0 {
+                            pending.extend([
+                                Piece::Text(" "),
+                                Piece::Text(symbol),
+                                Piece::Text(" "),
+                            ]);
+                        }
+                    }
+                }
+                _ => {
+                    pending.push(Piece::Text(")"));
+
+                    for (index, packet) in packets.iter().enumerate().rev() {
+                        pending.push(Piece::Packet(packet));
+
+                        if index > 0 {
+                            pending.push(Piece::Text(", "));
+                        }
+                    }
+
+                    pending.extend([Piece::Text("("), Piece::Text(operator_type.name())]);
+                }
+            },
+        )
+    }
+}
+
+impl Display for SExpr<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        render(f, self.0, |pending, _, operator_type, packets| {
+            pending.push(Piece::Text(")"));
+
+            for packet in packets.iter().rev() {
+                pending.extend([Piece::Packet(packet), Piece::Text(" ")]);
+            }
+
+            pending.extend([
+                Piece::Text(
+                    operator_type
+                        .symbol()
+                        .unwrap_or_else(|| operator_type.name()),
+                ),
+                Piece::Text("("),
+            ]);
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::day16::data::{OperatorType, Packet};
+    use crate::day16::strategy::{literal, operator};
+    use test_case::test_case;
+
+    fn example() -> Packet {
+        operator(
+            OperatorType::Maximum,
+            vec![
+                operator(OperatorType::Sum, vec![literal(3), literal(4)]),
+                operator(OperatorType::Product, vec![literal(2), literal(5)]),
+            ],
+        )
+    }
+
+    #[test]
+    fn infix() {
+        assert_eq!(example().infix().to_string(), "max(3 + 4, 2 * 5)");
+    }
+
+    #[test]
+    fn sexpr() {
+        assert_eq!(example().sexpr().to_string(), "(max (+ 3 4) (* 2 5))");
+    }
+
+    #[test_case(vec![literal(1)] => "sum(1)" ; "single operand")]
+    #[test_case(vec![literal(1), example()] => "1 + max(3 + 4, 2 * 5)" ; "function operand")]
+    #[test_case(
+        vec![operator(OperatorType::LessThan, vec![literal(1), literal(2)]), literal(3)]
+        => "(1 < 2) + 3" ; "infix operand"
+    )]
+    fn infix_sum(packets: Vec<Packet>) -> String {
+        operator(OperatorType::Sum, packets).infix().to_string()
+    }
+
+    #[test_case(vec![literal(1), literal(2)] => "1 < 2" ; "two operands")]
+    #[test_case(vec![literal(1), literal(2), literal(3)] => "lt(1, 2, 3)" ; "three operands")]
+    #[test_case(vec![literal(1)] => "lt(1)" ; "single operand")]
+    fn infix_comparison(packets: Vec<Packet>) -> String {
+        operator(OperatorType::LessThan, packets)
+            .infix()
+            .to_string()
+    }
+}
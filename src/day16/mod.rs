@@ -0,0 +1,351 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 16 of Advent of Code 2021
+
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+use anyhow::Context;
+use bignum::BigUint;
+use eval::EvaluationError;
+use std::io::{Read, Write};
+use std::str::FromStr;
+use vm::Program;
+
+pub mod bignum;
+pub mod data;
+pub mod disasm;
+pub mod encoder;
+pub mod eval;
+pub mod expression;
+pub mod parser;
+#[cfg(test)]
+mod strategy;
+pub mod stream;
+pub mod vm;
+
+pub const DAY: Day = Day {
+    number: 16,
+    inputs: lazy_input!(16),
+    embedded: embedded_input!(16, "answers.toml", "input.txt"),
+    default_inputs: &["input"],
+    solution: &Solver {
+        arbitrary_precision: false,
+    },
+};
+
+pub struct Solver {
+    /// Evaluates the expression without overflowing, answering with text when it exceeds a u64
+    pub arbitrary_precision: bool,
+}
+
+impl Solution for Solver {
+    type Input = data::Packet;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        let mut packets = stream::Decoder::new(content.as_bytes()).packets();
+        let packet = packets
+            .next()
+            .context("the input holds no transmission")??;
+
+        anyhow::ensure!(
+            packets.next().is_none(),
+            "the input holds more than one transmission"
+        );
+
+        Ok(packet)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        if !self.arbitrary_precision {
+            return Ok(challenge_two(input)?.into());
+        }
+
+        let value = Program::compile(input).evaluate::<BigUint>()?;
+
+        Ok(value
+            .to_u64()
+            .map_or_else(|| Answer::Text(value.to_string()), Answer::from))
+    }
+}
+
+/// How to show a transmission when inspecting it
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum View {
+    /// Every packet with its position and bits, see [`disasm::disassemble`]
+    Disassembly,
+    /// The expression evaluated by the packets, with infix operators
+    Infix,
+    /// The expression evaluated by the packets, as an S-expression
+    SExpr,
+    /// The value of the expression, evaluated with arbitrary precision
+    Value,
+    /// Every instruction run to evaluate the expression along with the stack after it
+    Trace,
+}
+
+impl FromStr for View {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "disasm" => Ok(Self::Disassembly),
+            "infix" => Ok(Self::Infix),
+            "sexpr" => Ok(Self::SExpr),
+            "value" => Ok(Self::Value),
+            "trace" => Ok(Self::Trace),
+            _ => anyhow::bail!(
+                "unknown view {:?}, expected `disasm`, `infix`, `sexpr`, `value` or `trace`",
+                s
+            ),
+        }
+    }
+}
+
+/// Renders every hexadecimal transmission of a reader in the given view, writing each one out as
+/// soon as it is decoded
+///
+/// # Errors
+/// An error is returned if the reader or the output fails, or if a transmission does not hold a
+/// valid packet
+pub fn inspect(transmissions: impl Read, view: View, mut output: impl Write) -> anyhow::Result<()> {
+    if view == View::Disassembly {
+        writeln!(output, "offset  v  t op      content          bits")?;
+
+        for line in disasm::disassemble(transmissions) {
+            writeln!(output, "{}", line?)?;
+        }
+
+        return Ok(());
+    }
+
+    for packet in stream::Decoder::new(transmissions).packets() {
+        let packet = packet?;
+
+        match view {
+            View::Disassembly => unreachable!("disassembly is listed from the events"),
+            View::Infix => writeln!(output, "{}", packet.infix())?,
+            View::SExpr => writeln!(output, "{}", packet.sexpr())?,
+            View::Value => writeln!(
+                output,
+                "{}",
+                Program::compile(&packet).evaluate::<BigUint>()?
+            )?,
+            View::Trace => {
+                let mut written = Ok(());
+                let result = Program::compile(&packet).run::<BigUint, _>(|step| {
+                    if written.is_ok() {
+                        written = writeln!(output, "{}", step);
+                    }
+                });
+
+                written?;
+
+                match result {
+                    Ok(value) => writeln!(output, "= {}", value)?,
+                    Err(error) => writeln!(output, "! {}", error)?,
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn challenge_one(input: &data::Packet) -> usize {
+    input
+        .linearize()
+        .into_iter()
+        .map(|x| x.version() as usize)
+        .sum()
+}
+
+fn challenge_two(input: &data::Packet) -> Result<u64, EvaluationError> {
+    Program::compile(input).evaluate()
+}
+
+#[cfg(test)]
+mod test {
+    use super::challenge_one;
+    use super::challenge_two;
+    use super::data::{OperatorType, Packet};
+    use super::encoder::Encoder;
+    use super::parser::parse_packet_bits;
+    use super::{inspect, Solver, View};
+    use crate::nom::parse_all_bits;
+    use crate::{Answer, Solution};
+
+    macro_rules! test_challenge {
+        ($name: ident, $func: ident($input: literal) => $value: expr) => {
+            #[test]
+            fn $name() {
+                let data = hex::decode($input).unwrap();
+                let packet = parse_all_bits(parse_packet_bits, &data).unwrap();
+
+                let result = $func(&packet);
+
+                assert_eq!(result, $value);
+                assert_eq!(
+                    super::Program::compile(&packet).evaluate::<u64>(),
+                    packet.evaluate_tree()
+                );
+            }
+        };
+    }
+
+    test_challenge!(
+        test_challenge_one_1,
+        challenge_one("8A004A801A8002F478") => 16
+    );
+
+    test_challenge!(
+        test_challenge_one_2,
+        challenge_one("620080001611562C8802118E34") => 12
+    );
+
+    test_challenge!(
+        test_challenge_one_3,
+        challenge_one("C0015000016115A2E0802F182340") => 23
+    );
+
+    test_challenge!(
+        test_challenge_one_4,
+        challenge_one("A0016C880162017C3686B18A3D4780") => 31
+    );
+
+    test_challenge!(
+        test_challenge_two_1,
+        challenge_two("C200B40A82") => Ok(3)
+    );
+
+    test_challenge!(
+        test_challenge_two_2,
+        challenge_two("04005AC33890") => Ok(54)
+    );
+
+    test_challenge!(
+        test_challenge_two_3,
+        challenge_two("880086C3E88112") => Ok(7)
+    );
+
+    test_challenge!(
+        test_challenge_two_4,
+        challenge_two("CE00C43D881120") => Ok(9)
+    );
+
+    test_challenge!(
+        test_challenge_two_5,
+        challenge_two("D8005AC2A8F0") => Ok(1)
+    );
+
+    test_challenge!(
+        test_challenge_two_6,
+        challenge_two("F600BC2D8F") => Ok(0)
+    );
+
+    test_challenge!(
+        test_challenge_two_7,
+        challenge_two("9C005AC2F8F0") => Ok(0)
+    );
+
+    test_challenge!(
+        test_challenge_two_8,
+        challenge_two("9C0141080250320F1802104A08") => Ok(1)
+    );
+
+    #[test]
+    fn arbitrary_precision() {
+        let packet = Packet::Operator {
+            version: 0,
+            operator_type: OperatorType::Product,
+            packets: vec![
+                Packet::Literal {
+                    version: 0,
+                    value: u64::MAX,
+                },
+                Packet::Literal {
+                    version: 0,
+                    value: 10,
+                },
+            ],
+        };
+        let transmission = Encoder::default().encode_hex(&packet).unwrap();
+
+        let fixed = Solver {
+            arbitrary_precision: false,
+        };
+        let arbitrary = Solver {
+            arbitrary_precision: true,
+        };
+        let input = fixed.parse(&transmission).unwrap();
+
+        assert!(fixed.challenge_two(&input).is_err());
+        assert_eq!(
+            arbitrary.challenge_two(&input).unwrap(),
+            Answer::Text("184467440737095516150".to_string())
+        );
+    }
+
+    #[test]
+    fn deep_nesting() {
+        // Sums of a single sub-packet, counted in packets, nested around a literal 1
+        let mut bits = "000000100000000001".repeat(100_000);
+        bits.push_str("00010000001");
+        while !bits.len().is_multiple_of(8) {
+            bits.push('0');
+        }
+
+        let transmission = bits
+            .as_bytes()
+            .chunks(4)
+            .map(|nibble| {
+                let nibble = std::str::from_utf8(nibble).unwrap();
+                format!("{:X}", u8::from_str_radix(nibble, 2).unwrap())
+            })
+            .collect::<String>();
+
+        let solver = Solver {
+            arbitrary_precision: false,
+        };
+        let input = solver.parse(&transmission).unwrap();
+
+        assert_eq!(solver.challenge_one(&input).unwrap(), Answer::from(0_usize));
+        assert_eq!(solver.challenge_two(&input).unwrap(), Answer::from(1_u64));
+
+        for view in [
+            View::Disassembly,
+            View::Infix,
+            View::SExpr,
+            View::Value,
+            View::Trace,
+        ] {
+            assert!(inspect(transmission.as_bytes(), view, std::io::sink()).is_ok());
+        }
+    }
+}
@@ -22,124 +22,84 @@
  * SOFTWARE.
  */
 
-use crate::data::{OperatorType, Packet};
-use aoc2021::nom::ParseResult;
-use nom::{bits, combinator::map, multi, sequence, InputLength};
-
-enum OperatorLength {
-    Bits(usize),
-    Packets(usize),
+use super::data::Packet;
+use super::stream::{BitRead, DecodeError, PacketBuilder, PacketDecoder};
+use crate::nom::{BitInput, ParseResult};
+use nom::error::{VerboseError, VerboseErrorKind};
+
+/// Bits of a byte slice, positions counting from its start
+struct SliceBits<'a> {
+    data: &'a [u8],
+    position: usize,
 }
 
-type BitInput<'a> = (&'a [u8], usize);
-
-fn parse_literal_value(data: BitInput) -> ParseResult<u64, BitInput> {
-    let (remainder, result): (BitInput, Vec<u8>) = multi::many0(sequence::preceded(
-        bits::complete::tag(1usize, 1usize),
-        bits::complete::take(4usize),
-    ))(data)?;
-
-    let (remainder, last_part): (BitInput, u8) = sequence::preceded(
-        bits::complete::tag(0usize, 1usize),
-        bits::complete::take(4_u8),
-    )(remainder)?;
-
-    let mut value = 0_u64;
-
-    for part in result {
-        value = (value << 4) | u64::from(part);
+impl<'a> SliceBits<'a> {
+    fn input(&self) -> BitInput<'a> {
+        (&self.data[self.position / 8..], self.position % 8)
     }
-
-    value = (value << 4) | u64::from(last_part);
-
-    Ok((remainder, value))
 }
 
-fn parse_operator_length(data: BitInput) -> ParseResult<OperatorLength, BitInput> {
-    let (remainder, tag): (BitInput, u8) = bits::complete::take(1usize)(data)?;
+impl BitRead for SliceBits<'_> {
+    type Error = DecodeError;
 
-    match tag {
-        0 => map(bits::complete::take(15usize), OperatorLength::Bits)(remainder),
-        1 => map(bits::complete::take(11usize), OperatorLength::Packets)(remainder),
-        _ => unreachable!(),
+    fn position(&self) -> usize {
+        self.position
     }
-}
 
-fn parse_operator_subpackets(data: BitInput) -> ParseResult<Vec<Packet>, BitInput> {
-    let (remainder, length) = parse_operator_length(data)?;
-
-    match length {
-        OperatorLength::Bits(length) => {
-            if remainder.input_len() < length {
-                return Err(nom::Err::Error(nom::error::VerboseError {
-                    errors: vec![(
-                        remainder,
-                        nom::error::VerboseErrorKind::Context("not enough data to create packets"),
-                    )],
-                }));
-            }
-
-            let target_remainder_length = remainder.input_len() - length;
-
-            let mut iterator = nom::combinator::iterator(
-                remainder,
-                sequence::terminated(
-                    parse_packet_inner,
-                    nom::combinator::verify(
-                        nom::combinator::peek(nom::combinator::rest_len),
-                        |&len| len >= target_remainder_length,
-                    ),
-                ),
-            );
+    fn read(&mut self, width: usize) -> Result<u64, DecodeError> {
+        if self.position + width > self.data.len() * 8 {
+            return Err(DecodeError::Truncated);
+        }
 
-            let packets: Vec<Packet> = iterator.collect();
+        let mut value = 0;
 
-            iterator
-                .finish()
-                .map(move |(remainder, _)| (remainder, packets))
+        for _ in 0..width {
+            let bit = self.data[self.position / 8] >> (7 - self.position % 8) & 1;
+            value = value << 1 | u64::from(bit);
+            self.position += 1;
         }
-        OperatorLength::Packets(length) => multi::count(parse_packet_inner, length)(remainder),
-    }
-}
 
-fn parse_u3_tag(data: BitInput) -> ParseResult<u8, BitInput> {
-    bits::complete::take(3usize)(data)
+        Ok(value)
+    }
 }
 
-fn parse_packet_inner(data: BitInput) -> ParseResult<Packet, BitInput> {
-    let (remainder, (version, type_id)) = sequence::pair(parse_u3_tag, parse_u3_tag)(data)?;
+/// Parses a packet at the bit level, leaving any padding after it unconsumed. Operators are kept
+/// on a stack by [`PacketDecoder`], so deeply nested packets do not recurse.
+pub fn parse_packet_bits(data: BitInput) -> ParseResult<Packet, BitInput> {
+    let mut bits = SliceBits {
+        data: data.0,
+        position: data.1,
+    };
+    let mut decoder = PacketDecoder::default();
+    let mut builder = PacketBuilder::default();
+
+    loop {
+        let event = match decoder.next_event(&mut bits) {
+            Ok(event) => event.expect("the packet is built by the last event of the decoder"),
+            Err(error) => {
+                return Err(nom::Err::Error(VerboseError {
+                    errors: vec![(bits.input(), VerboseErrorKind::Context(error.message()))],
+                }))
+            }
+        };
 
-    // Literal packet fast-path
-    if type_id == 4u8 {
-        return map(parse_literal_value, |value| Packet::Literal {
-            version,
-            value,
-        })(remainder);
+        if let Some(packet) = builder.push(event) {
+            return Ok((bits.input(), packet));
+        }
     }
-
-    nom::combinator::map_res(parse_operator_subpackets, move |packets| {
-        OperatorType::try_from(type_id).map(|operator_type| Packet::Operator {
-            version,
-            operator_type,
-            packets,
-        })
-    })(remainder)
-}
-
-pub fn parse_packet(data: &[u8]) -> ParseResult<Packet, &[u8]> {
-    bits(parse_packet_inner)(data)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::day16::data::OperatorType;
 
     macro_rules! test_packet {
         ($name: ident, $input: literal, $packet: expr) => {
             #[test]
             fn $name() {
                 let data = hex::decode($input).unwrap();
-                let (_, packet) = parse_packet(&data).unwrap();
+                let (_, packet) = parse_packet_bits((&data, 0)).unwrap();
 
                 let expected = $packet;
 
@@ -177,6 +137,36 @@ mod test {
         }
     );
 
+    #[test]
+    fn literal_wider_than_64_bits() {
+        // 16 groups holding u64::MAX, and the same with a 17th group
+        let widest = format!("110100{}{}", "11111".repeat(15), "01111");
+        let wider = format!("110100{}{}", "11111".repeat(16), "00000");
+
+        let parse = |bits: &str| {
+            let mut bits = bits.to_string();
+            while !bits.len().is_multiple_of(8) {
+                bits.push('0');
+            }
+
+            let data = (0..bits.len())
+                .step_by(8)
+                .map(|index| u8::from_str_radix(&bits[index..index + 8], 2).unwrap())
+                .collect::<Vec<_>>();
+
+            parse_packet_bits((&data, 0)).ok().map(|(_, packet)| packet)
+        };
+
+        assert_eq!(
+            parse(&widest).unwrap(),
+            Packet::Literal {
+                version: 6,
+                value: u64::MAX
+            }
+        );
+        assert!(parse(&wider).is_none());
+    }
+
     test_packet!(
         operator_packet_length_type,
         "EE00D40C823060",
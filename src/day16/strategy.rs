@@ -0,0 +1,68 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Strategies generating random packets for property tests, and helpers building fixed ones
+
+use super::data::{OperatorType, Packet};
+use proptest::prelude::*;
+
+pub fn operator_type() -> impl Strategy<Value = OperatorType> {
+    prop_oneof![
+        Just(OperatorType::Sum),
+        Just(OperatorType::Product),
+        Just(OperatorType::Minimum),
+        Just(OperatorType::Maximum),
+        Just(OperatorType::GreaterThan),
+        Just(OperatorType::LessThan),
+        Just(OperatorType::EqualTo),
+    ]
+}
+
+/// Any packet tree, not necessarily a valid expression
+pub fn packet() -> impl Strategy<Value = Packet> {
+    let literal =
+        (0..8u8, any::<u64>()).prop_map(|(version, value)| Packet::Literal { version, value });
+
+    literal.prop_recursive(4, 64, 8, |inner| {
+        (0..8u8, operator_type(), prop::collection::vec(inner, 0..8)).prop_map(
+            |(version, operator_type, packets)| Packet::Operator {
+                version,
+                operator_type,
+                packets,
+            },
+        )
+    })
+}
+
+pub fn literal(value: u64) -> Packet {
+    Packet::Literal { version: 0, value }
+}
+
+pub fn operator(operator_type: OperatorType, packets: Vec<Packet>) -> Packet {
+    Packet::Operator {
+        version: 0,
+        operator_type,
+        packets,
+    }
+}
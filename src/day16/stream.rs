@@ -0,0 +1,561 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Streaming decoder reading BITS transmissions from any reader, one hex digit at a time
+//!
+//! Transmissions are separated by whitespace, each holding a single packet followed by the
+//! padding completing its last byte. Only the operators currently open are kept in memory.
+
+use super::data::{OperatorType, Packet, LITERAL_TYPE};
+use super::encoder::LengthType;
+use anyhow::{bail, Context};
+use std::fmt::{Display, Formatter};
+use std::io::{BufReader, Bytes, Read};
+use std::ops::Range;
+
+/// Part of a packet, as soon as it is decoded
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Event {
+    Literal {
+        version: u8,
+        value: u64,
+    },
+    /// Start of an operator, followed by the events of its sub-packets and an [`Event::End`]
+    Begin {
+        version: u8,
+        operator_type: OperatorType,
+        length_type: LengthType,
+        length: usize,
+    },
+    End,
+}
+
+/// Reason the bits of a transmission do not make a valid packet
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DecodeError {
+    Truncated,
+    LiteralOverflow,
+    UnknownType,
+    Overrun,
+}
+
+impl DecodeError {
+    /// Description of the error, also used as the context of parse errors
+    pub fn message(self) -> &'static str {
+        match self {
+            DecodeError::Truncated => "transmission ends in the middle of a packet",
+            DecodeError::LiteralOverflow => "literal value does not fit in 64 bits",
+            DecodeError::UnknownType => "unknown packet type",
+            DecodeError::Overrun => "sub-packets overrun their length",
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Bits of a single transmission, read from its start
+pub(super) trait BitRead {
+    type Error: From<DecodeError>;
+
+    /// Number of bits read so far
+    fn position(&self) -> usize;
+
+    /// Reads the next `width` bits as a number, most significant first
+    fn read(&mut self, width: usize) -> Result<u64, Self::Error>;
+}
+
+/// Where an open operator stops
+#[derive(Debug, Copy, Clone)]
+enum Limit {
+    /// Bit position, in the transmission, right after the last sub-packet
+    Bits(usize),
+    /// Number of sub-packets yet to start
+    Packets(usize),
+}
+
+/// State machine decoding a single packet into events, keeping the open operators on a stack
+/// instead of recursing into them
+#[derive(Debug, Default)]
+pub(super) struct PacketDecoder {
+    /// Limits of the operators that are still open, innermost last
+    open: Vec<Limit>,
+    /// Whether the packet was started
+    started: bool,
+}
+
+impl PacketDecoder {
+    /// Decodes the next event of the packet, none once the packet is complete
+    ///
+    /// # Errors
+    /// An error is returned if the bits cannot be read or do not make a valid packet
+    pub(super) fn next_event<B: BitRead>(
+        &mut self,
+        bits: &mut B,
+    ) -> Result<Option<Event>, B::Error> {
+        if let Some(&limit) = self.open.last() {
+            let done = match limit {
+                Limit::Bits(end) if bits.position() > end => {
+                    return Err(DecodeError::Overrun.into())
+                }
+                Limit::Bits(end) => bits.position() == end,
+                Limit::Packets(remaining) => remaining == 0,
+            };
+
+            if done {
+                self.open.pop();
+                return Ok(Some(Event::End));
+            }
+        } else if self.started {
+            return Ok(None);
+        }
+
+        self.started = true;
+
+        if let Some(Limit::Packets(remaining)) = self.open.last_mut() {
+            *remaining -= 1;
+        }
+
+        let version = bits.read(3)? as u8;
+        let type_id = bits.read(3)? as u8;
+
+        if type_id == LITERAL_TYPE {
+            let mut value = 0_u64;
+
+            loop {
+                let more = bits.read(1)? == 1;
+
+                value =
+                    value.checked_mul(16).ok_or(DecodeError::LiteralOverflow)? | bits.read(4)?;
+
+                if !more {
+                    break;
+                }
+            }
+
+            return Ok(Some(Event::Literal { version, value }));
+        }
+
+        let operator_type =
+            OperatorType::try_from(type_id).map_err(|_| DecodeError::UnknownType)?;
+
+        let length_type = if bits.read(1)? == 0 {
+            LengthType::Bits
+        } else {
+            LengthType::Packets
+        };
+        let length = bits.read(length_type.width())? as usize;
+
+        self.open.push(match length_type {
+            LengthType::Bits => Limit::Bits(bits.position() + length),
+            LengthType::Packets => Limit::Packets(length),
+        });
+
+        Ok(Some(Event::Begin {
+            version,
+            operator_type,
+            length_type,
+            length,
+        }))
+    }
+}
+
+/// Builds packets from their events, keeping the operators being built on a stack
+#[derive(Debug, Default)]
+pub(super) struct PacketBuilder {
+    /// Operators being built, innermost last
+    open: Vec<(u8, OperatorType, Vec<Packet>)>,
+}
+
+impl PacketBuilder {
+    /// Adds the next event, returning the top-level packet it completes if any
+    pub(super) fn push(&mut self, event: Event) -> Option<Packet> {
+        let packet = match event {
+            Event::Literal { version, value } => Packet::Literal { version, value },
+            Event::Begin {
+                version,
+                operator_type,
+                ..
+            } => {
+                self.open.push((version, operator_type, Vec::new()));
+                return None;
+            }
+            Event::End => {
+                let (version, operator_type, packets) = self
+                    .open
+                    .pop()
+                    .expect("the decoder only ends operators it began");
+
+                Packet::Operator {
+                    version,
+                    operator_type,
+                    packets,
+                }
+            }
+        };
+
+        match self.open.last_mut() {
+            Some((_, _, packets)) => {
+                packets.push(packet);
+                None
+            }
+            None => Some(packet),
+        }
+    }
+}
+
+/// Bits of the hex digits of a reader, counted from the start of the current transmission
+struct HexBits<R: Read> {
+    bytes: Bytes<BufReader<R>>,
+    peeked: Option<u8>,
+    digit: u8,
+    bits_left: usize,
+    digits: usize,
+}
+
+impl<R: Read> HexBits<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            bytes: BufReader::new(reader).bytes(),
+            peeked: None,
+            digit: 0,
+            bits_left: 0,
+            digits: 0,
+        }
+    }
+
+    fn peek(&mut self) -> anyhow::Result<Option<u8>> {
+        if self.peeked.is_none() {
+            self.peeked = self.bytes.next().transpose()?;
+        }
+
+        Ok(self.peeked)
+    }
+
+    /// Consumes the next hex digit of the transmission, if the transmission is not over
+    fn next_digit(&mut self) -> anyhow::Result<Option<u8>> {
+        let byte = match self.peek()? {
+            None => return Ok(None),
+            Some(byte) if byte.is_ascii_whitespace() => return Ok(None),
+            Some(byte) => byte,
+        };
+
+        let digit = char::from(byte)
+            .to_digit(16)
+            .with_context(|| format!("invalid hex digit {:?}", char::from(byte)))?;
+
+        self.peeked = None;
+        self.digits += 1;
+
+        Ok(Some(digit as u8))
+    }
+
+    /// Skips whitespace up to the next transmission, returning whether there is one
+    fn start(&mut self) -> anyhow::Result<bool> {
+        while let Some(byte) = self.peek()? {
+            if !byte.is_ascii_whitespace() {
+                self.digits = 0;
+                self.bits_left = 0;
+
+                return Ok(true);
+            }
+
+            self.peeked = None;
+        }
+
+        Ok(false)
+    }
+
+    /// Skips the padding of the last byte, which must end the transmission
+    fn finish(&mut self) -> anyhow::Result<()> {
+        self.bits_left = 0;
+
+        if self.digits % 2 == 1 && self.next_digit()?.is_none() {
+            bail!("transmission has an odd number of hex digits");
+        }
+
+        if self.next_digit()?.is_some() {
+            bail!("unexpected data after the packet");
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> BitRead for HexBits<R> {
+    type Error = anyhow::Error;
+
+    fn position(&self) -> usize {
+        self.digits * 4 - self.bits_left
+    }
+
+    fn read(&mut self, width: usize) -> anyhow::Result<u64> {
+        let mut value = 0;
+
+        for _ in 0..width {
+            if self.bits_left == 0 {
+                self.digit = self.next_digit()?.ok_or(DecodeError::Truncated)?;
+                self.bits_left = 4;
+            }
+
+            self.bits_left -= 1;
+            value = value << 1 | u64::from(self.digit >> self.bits_left & 1);
+        }
+
+        Ok(value)
+    }
+}
+
+/// Decodes the packets of the transmissions of a reader
+pub struct Decoder<R: Read> {
+    bits: HexBits<R>,
+    /// Decoder of the packet of the current transmission, if one was started
+    packet: Option<PacketDecoder>,
+}
+
+impl<R: Read> Decoder<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            bits: HexBits::new(reader),
+            packet: None,
+        }
+    }
+
+    /// Decodes the next event of the stream, if any
+    ///
+    /// # Errors
+    /// An error is returned if the reader fails or a transmission is not valid, with the bit
+    /// position of the failure within its transmission
+    pub fn next_event(&mut self) -> anyhow::Result<Option<Event>> {
+        Ok(self.next_spanned_event()?.map(|(event, _)| event))
+    }
+
+    /// Decodes the next event of the stream along with the bits it was decoded from, counted
+    /// from the start of its transmission
+    ///
+    /// # Errors
+    /// An error is returned if the reader fails or a transmission is not valid, see
+    /// [`Decoder::next_event`]
+    pub fn next_spanned_event(&mut self) -> anyhow::Result<Option<(Event, Range<usize>)>> {
+        self.decode()
+            .with_context(|| format!("decoding bit {}", self.bits.position()))
+    }
+
+    fn decode(&mut self) -> anyhow::Result<Option<(Event, Range<usize>)>> {
+        loop {
+            let packet = match &mut self.packet {
+                Some(packet) => packet,
+                None if self.bits.start()? => self.packet.insert(PacketDecoder::default()),
+                None => return Ok(None),
+            };
+
+            let start = self.bits.position();
+
+            if let Some(event) = packet.next_event(&mut self.bits)? {
+                return Ok(Some((event, start..self.bits.position())));
+            }
+
+            self.bits.finish()?;
+            self.packet = None;
+        }
+    }
+
+    /// Iterator over the packet of each transmission, built from the events as they complete
+    pub fn packets(self) -> Packets<R> {
+        Packets {
+            decoder: self,
+            builder: PacketBuilder::default(),
+        }
+    }
+}
+
+/// Top-level packets of a [`Decoder`]
+pub struct Packets<R: Read> {
+    decoder: Decoder<R>,
+    builder: PacketBuilder,
+}
+
+impl<R: Read> Iterator for Packets<R> {
+    type Item = anyhow::Result<Packet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.decoder.next_event() {
+                Err(error) => return Some(Err(error)),
+                Ok(None) => return None,
+                Ok(Some(event)) => {
+                    if let Some(packet) = self.builder.push(event) {
+                        return Some(Ok(packet));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day16::encoder::Encoder;
+    use crate::day16::parser::parse_packet_bits;
+    use crate::day16::strategy::packet;
+    use crate::nom::parse_all_bits;
+    use proptest::prelude::*;
+    use test_case::test_case;
+
+    const TRANSMISSIONS: &[&str] = &[
+        "D2FE28",
+        "38006F45291200",
+        "EE00D40C823060",
+        "8A004A801A8002F478",
+        "620080001611562C8802118E34",
+        "C0015000016115A2E0802F182340",
+        "A0016C880162017C3686B18A3D4780",
+        "9C0141080250320F1802104A08",
+    ];
+
+    #[test]
+    fn concatenated() {
+        let stream = TRANSMISSIONS.join("\n");
+
+        let packets = Decoder::new(stream.as_bytes())
+            .packets()
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+
+        let expected = TRANSMISSIONS
+            .iter()
+            .map(|transmission| {
+                parse_all_bits(parse_packet_bits, &hex::decode(transmission).unwrap()).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(packets, expected);
+    }
+
+    #[test]
+    fn events() {
+        let mut decoder = Decoder::new("38006F45291200\n".as_bytes());
+
+        let events = std::iter::from_fn(|| decoder.next_event().unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            vec![
+                Event::Begin {
+                    version: 1,
+                    operator_type: OperatorType::LessThan,
+                    length_type: LengthType::Bits,
+                    length: 27
+                },
+                Event::Literal {
+                    version: 6,
+                    value: 10
+                },
+                Event::Literal {
+                    version: 2,
+                    value: 20
+                },
+                Event::End,
+            ]
+        );
+    }
+
+    #[test_case("38006F452912"  ; "truncated")]
+    #[test_case("D2FE28X"       ; "invalid digit")]
+    #[test_case("D2FE2800"      ; "trailing data")]
+    #[test_case("D2FE2"         ; "odd digits")]
+    #[test_case("D2FE2800 D2FE" ; "second transmission")]
+    fn invalid(stream: &str) {
+        let result = Decoder::new(stream.as_bytes())
+            .packets()
+            .collect::<anyhow::Result<Vec<_>>>();
+
+        assert!(result.is_err());
+    }
+
+    /// Packet of a transmission according to the stream decoder and to the nom parser
+    fn decode_both(bytes: &[u8]) -> (Option<Packet>, Option<Packet>) {
+        let transmission = hex::encode(bytes);
+        let mut packets = Decoder::new(transmission.as_bytes()).packets();
+
+        let streamed = match (packets.next(), packets.next()) {
+            (Some(Ok(packet)), None) => Some(packet),
+            _ => None,
+        };
+        let parsed = parse_all_bits(parse_packet_bits, bytes).ok();
+
+        (streamed, parsed)
+    }
+
+    #[test_case("D3FFFFFFFFFFFFFFFFFFBC" => true ; "u64 max")]
+    #[test_case("D3FFFFFFFFFFFFFFFFFFFC00" => false ; "wider than 64 bits")]
+    fn literal_width(transmission: &str) -> bool {
+        let (streamed, parsed) = decode_both(&hex::decode(transmission).unwrap());
+
+        assert_eq!(streamed, parsed);
+        streamed.is_some()
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_parser(bytes in prop::collection::vec(any::<u8>(), 1..24)) {
+            let (streamed, parsed) = decode_both(&bytes);
+
+            prop_assert_eq!(streamed, parsed);
+        }
+
+        #[test]
+        fn agrees_with_parser_on_corrupted(packet in packet(), bit in any::<prop::sample::Index>()) {
+            let mut bytes = Encoder::default().encode(&packet).unwrap();
+            let bit = bit.index(bytes.len() * 8);
+            bytes[bit / 8] ^= 0x80 >> (bit % 8);
+
+            let (streamed, parsed) = decode_both(&bytes);
+
+            prop_assert_eq!(streamed, parsed);
+        }
+
+        #[test]
+        fn decodes_encoded(packets in prop::collection::vec(packet(), 1..4)) {
+            let stream = packets
+                .iter()
+                .map(|packet| Encoder::default().encode_hex(packet).unwrap())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let decoded = Decoder::new(stream.as_bytes())
+                .packets()
+                .collect::<anyhow::Result<Vec<_>>>()
+                .unwrap();
+
+            prop_assert_eq!(decoded, packets);
+        }
+    }
+}
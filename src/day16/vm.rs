@@ -0,0 +1,327 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Compilation of packets into a flat stack machine program, evaluated without recursion
+
+use super::data::{OperatorType, Packet};
+use super::eval::{apply, EvaluationError, PacketPath, Value};
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instruction {
+    /// Pushes a literal value on the stack
+    Push(u64),
+    /// Pops the given number of operands and pushes the result of the operation on them
+    Apply {
+        operator_type: OperatorType,
+        operands: usize,
+    },
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Push(value) => write!(f, "push {}", value),
+            Instruction::Apply {
+                operator_type,
+                operands,
+            } => write!(f, "{} {}", operator_type.name(), operands),
+        }
+    }
+}
+
+/// Where the packet an instruction was compiled from sits in the tree
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Origin {
+    /// Instruction of the operator holding the packet, none for the root
+    parent: Option<usize>,
+    /// Index of the packet among the sub-packets of its operator
+    index: usize,
+}
+
+/// Instructions evaluating a packet, operands always coming before their operator
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Program {
+    instructions: Vec<Instruction>,
+    /// Origin of each instruction, to rebuild the path of a packet when reporting an error
+    origins: Vec<Origin>,
+}
+
+/// State of the machine right after running an instruction
+#[derive(Debug)]
+pub struct TraceStep<'a, V> {
+    pub instruction: &'a Instruction,
+    pub stack: &'a [V],
+}
+
+impl<V: Display> Display for TraceStep<'_, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:<16} [{}]",
+            self.instruction.to_string(),
+            self.stack.iter().join(", ")
+        )
+    }
+}
+
+impl Program {
+    /// Compiles a packet, visiting its tree in post-order with an explicit stack
+    pub fn compile(packet: &Packet) -> Self {
+        let mut program = Program {
+            instructions: Vec::new(),
+            origins: Vec::new(),
+        };
+        let mut pending = vec![(packet, 0, false)];
+        // Instructions whose operator is not compiled yet, in the order they will be its operands
+        let mut orphans: Vec<usize> = Vec::new();
+
+        while let Some((packet, index, visited)) = pending.pop() {
+            let instruction = match packet {
+                Packet::Literal { value, .. } => Instruction::Push(*value),
+                Packet::Operator {
+                    operator_type,
+                    packets,
+                    ..
+                } => {
+                    if !visited {
+                        pending.push((packet, index, true));
+                        pending.extend(
+                            packets
+                                .iter()
+                                .enumerate()
+                                .rev()
+                                .map(|(index, packet)| (packet, index, false)),
+                        );
+
+                        continue;
+                    }
+
+                    let parent = program.instructions.len();
+                    for child in orphans.drain(orphans.len() - packets.len()..) {
+                        program.origins[child].parent = Some(parent);
+                    }
+
+                    Instruction::Apply {
+                        operator_type: *operator_type,
+                        operands: packets.len(),
+                    }
+                }
+            };
+
+            orphans.push(program.instructions.len());
+            program.instructions.push(instruction);
+            program.origins.push(Origin {
+                parent: None,
+                index,
+            });
+        }
+
+        program
+    }
+
+    /// Path of the packet an instruction was compiled from, following the parents up to the root
+    fn path(&self, instruction: usize) -> PacketPath {
+        let mut path = Vec::new();
+        let mut origin = self.origins[instruction];
+
+        while let Some(parent) = origin.parent {
+            path.push(origin.index);
+            origin = self.origins[parent];
+        }
+
+        path.reverse();
+        PacketPath(path)
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Runs the program, calling `observe` with the state of the machine after each instruction.
+    /// The steps end at the failing instruction if any.
+    ///
+    /// # Errors
+    /// An error is returned for the first operator that cannot be evaluated, see [`Packet::evaluate`]
+    pub fn run<V, F>(&self, mut observe: F) -> Result<V, EvaluationError>
+    where
+        V: Value,
+        F: FnMut(TraceStep<'_, V>),
+    {
+        let mut stack = Vec::new();
+
+        for (position, instruction) in self.instructions.iter().enumerate() {
+            match *instruction {
+                Instruction::Push(value) => stack.push(V::from(value)),
+                Instruction::Apply {
+                    operator_type,
+                    operands,
+                } => {
+                    let operands = stack.split_off(stack.len() - operands);
+                    stack.push(apply(operator_type, operands, || self.path(position))?);
+                }
+            }
+
+            observe(TraceStep {
+                instruction,
+                stack: &stack,
+            });
+        }
+
+        Ok(stack
+            .pop()
+            .expect("a compiled program always leaves its result on the stack"))
+    }
+
+    /// Evaluates the program, see [`Packet::evaluate`]
+    ///
+    /// # Errors
+    /// An error is returned for the first operator that cannot be evaluated
+    pub fn evaluate<V: Value>(&self) -> Result<V, EvaluationError> {
+        self.run(|_| {})
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day16::bignum::BigUint;
+    use crate::day16::strategy::{literal, operator, packet};
+    use proptest::prelude::*;
+
+    #[test]
+    fn compile() {
+        let packet = operator(
+            OperatorType::Maximum,
+            vec![
+                operator(OperatorType::Sum, vec![literal(3), literal(4)]),
+                literal(9),
+            ],
+        );
+
+        let program = Program::compile(&packet);
+
+        assert_eq!(
+            program.instructions().iter().join("; "),
+            "push 3; push 4; sum 2; push 9; max 2"
+        );
+        assert_eq!(program.evaluate::<u64>(), Ok(9));
+    }
+
+    #[test]
+    fn trace() {
+        let packet = operator(
+            OperatorType::Product,
+            vec![
+                literal(2),
+                operator(OperatorType::LessThan, vec![literal(1), literal(5)]),
+            ],
+        );
+
+        let mut steps = Vec::new();
+        let result = Program::compile(&packet).run::<u64, _>(|step| steps.push(step.to_string()));
+
+        assert_eq!(
+            steps,
+            vec![
+                "push 2           [2]",
+                "push 1           [2, 1]",
+                "push 5           [2, 1, 5]",
+                "lt 2             [2, 1]",
+                "product 2        [2]",
+            ]
+        );
+        assert_eq!(result, Ok(2));
+    }
+
+    #[test]
+    fn error_path() {
+        let packet = operator(
+            OperatorType::Sum,
+            vec![
+                literal(1),
+                operator(OperatorType::EqualTo, vec![literal(1)]),
+            ],
+        );
+
+        let mut steps = 0;
+        let result = Program::compile(&packet).run::<u64, _>(|_| steps += 1);
+
+        assert_eq!(steps, 2);
+        assert_eq!(result, packet.evaluate_tree());
+        assert_eq!(
+            result,
+            Err(EvaluationError::Arity {
+                path: PacketPath(vec![1]),
+                operator_type: OperatorType::EqualTo,
+                operands: 1
+            })
+        );
+    }
+
+    #[test]
+    fn nested_error_path() {
+        let packet = operator(
+            OperatorType::Maximum,
+            vec![
+                literal(1),
+                operator(
+                    OperatorType::Sum,
+                    vec![
+                        operator(OperatorType::LessThan, vec![literal(1), literal(2)]),
+                        operator(OperatorType::EqualTo, vec![]),
+                    ],
+                ),
+            ],
+        );
+
+        assert_eq!(
+            Program::compile(&packet).evaluate::<u64>(),
+            Err(EvaluationError::Empty {
+                path: PacketPath(vec![1, 1]),
+                operator_type: OperatorType::EqualTo,
+            })
+        );
+    }
+
+    #[test]
+    fn deep_nesting() {
+        let packet = (0..10_000).fold(literal(1), |packet, _| {
+            operator(OperatorType::Sum, vec![packet])
+        });
+
+        assert_eq!(Program::compile(&packet).evaluate::<u64>(), Ok(1));
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_tree(packet in packet()) {
+            let program = Program::compile(&packet);
+
+            prop_assert_eq!(program.evaluate::<u64>(), packet.evaluate_tree());
+            prop_assert_eq!(program.evaluate::<BigUint>(), packet.evaluate_tree());
+        }
+    }
+}
@@ -0,0 +1,216 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Pictures of the probe flying towards the target area
+
+use super::data::{Area, Moment, Point, Probe};
+use crate::grid::Grid;
+use crate::image::{Svg, WHITE};
+use crate::viz::{Color, Frame, Glyph};
+use itertools::Itertools;
+
+/// Largest size of the picture, the trajectory is scaled down to fit in it
+const MAX_WIDTH: usize = 100;
+const MAX_HEIGHT: usize = 50;
+
+/// Mapping from the coordinates of the trajectory to the cells of the picture
+struct Canvas {
+    min: Point,
+    max: Point,
+    width: usize,
+    height: usize,
+}
+
+impl Canvas {
+    /// Canvas covering the origin, the target area and the whole flight of the probe
+    fn new(probe: Probe, area: Area) -> Self {
+        let min = Point(0, area.min_y().min(0));
+        let max = Point(
+            area.max_x().max(probe.horizontal_apogee()),
+            area.max_y().max(probe.vertical_apogee()).max(0),
+        );
+
+        Self {
+            min,
+            max,
+            width: ((max.0 - min.0) as usize + 1).min(MAX_WIDTH),
+            height: ((max.1 - min.1) as usize + 1).min(MAX_HEIGHT),
+        }
+    }
+
+    /// Cell showing a point, with the vertical axis pointing up
+    fn cell(&self, Point(x, y): Point) -> Option<(usize, usize)> {
+        let scale = |offset: i64, span: i64, size: usize| {
+            usize::try_from(offset * (size as i64 - 1) / span.max(1)).ok()
+        };
+
+        if x < self.min.0 || x > self.max.0 || y < self.min.1 || y > self.max.1 {
+            return None;
+        }
+
+        Some((
+            scale(x - self.min.0, self.max.0 - self.min.0, self.width)?,
+            scale(self.max.1 - y, self.max.1 - self.min.1, self.height)?,
+        ))
+    }
+}
+
+/// Launch reaching the highest point while hitting the area, along with the time it hits
+fn highest_launch(area: Area) -> Option<(Probe, usize)> {
+    let max_y = area.max_y().abs().max(area.min_y().abs());
+
+    (0..=area.max_x())
+        .cartesian_product(-max_y..=max_y)
+        .filter_map(|(x, y)| {
+            let probe = Probe::launch(x, y);
+
+            probe.intersects(area).map(|time| (probe, time))
+        })
+        // The apogee formula is symmetric around zero, so compare the launch velocities instead
+        .max_by_key(|(probe, _)| probe.launch_velocity.1)
+}
+
+/// Drawing of the target area and the flight of the highest launch that hits it
+pub fn diagram(area: Area) -> Option<Svg> {
+    let (probe, hit) = highest_launch(area)?;
+    let canvas = Canvas::new(probe, area);
+
+    // SVG coordinates grow downwards, so heights are negated
+    let point = |Point(x, y): Point| (x as f64, -y as f64);
+
+    let margin = 1.;
+    let mut svg = Svg::new(
+        (canvas.min.0 as f64 - margin, -canvas.max.1 as f64 - margin),
+        (canvas.max.0 as f64 + margin, -canvas.min.1 as f64 + margin),
+    );
+    let width = svg.stroke(0.003);
+
+    svg.rect(
+        point(area.top_left),
+        point(area.bottom_right),
+        [205, 49, 49],
+        0.6,
+    );
+
+    let positions: Vec<_> = probe
+        .get_trajectory()
+        .take(hit + 1)
+        .map(|Moment { position, .. }| point(position))
+        .collect();
+
+    svg.polyline(&positions, [229, 229, 16], width);
+
+    for &position in &positions {
+        svg.circle(position, width * 1.5, WHITE);
+    }
+
+    Some(svg)
+}
+
+/// Frames of the highest launch that hits the area, one per step until it does
+pub fn trajectory(area: Area) -> Option<impl Iterator<Item = Frame>> {
+    let (probe, hit) = highest_launch(area)?;
+
+    let canvas = Canvas::new(probe, area);
+    let mut picture = Grid::filled(
+        canvas.width,
+        canvas.height,
+        Glyph::new('.', Color::DarkGray),
+    );
+
+    let corners = (
+        canvas.cell(Point(area.min_x(), area.max_y())),
+        canvas.cell(Point(area.max_x(), area.min_y())),
+    );
+    if let (Some((left, top)), Some((right, bottom))) = corners {
+        for position in (left..=right).cartesian_product(top..=bottom) {
+            picture[position] = Glyph::new('T', Color::Red);
+        }
+    }
+
+    let positions: Vec<_> = probe
+        .get_trajectory()
+        .take(hit + 1)
+        .map(|Moment { position, .. }| position)
+        .collect();
+
+    Some(
+        positions
+            .into_iter()
+            .enumerate()
+            .scan(picture, move |picture, (time, position)| {
+                let mut frame = picture.clone();
+
+                if let Some(cell) = canvas.cell(position) {
+                    picture[cell] = Glyph::new('#', Color::Yellow);
+                    frame[cell] = Glyph::new('o', Color::White);
+                }
+
+                Some(Frame {
+                    caption: format!(
+                        "launched at {:?}, step {}: {:?}",
+                        probe.launch_velocity, time, position
+                    ),
+                    cells: frame,
+                })
+            }),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn highest_launch() {
+        let area = Area::new(Point(20, -10), Point(30, -5));
+        let frames: Vec<_> = trajectory(area).unwrap().collect();
+
+        // Launched at 7,9 the probe reaches a height of 45 and hits the area on step 20
+        assert_eq!(frames.len(), 21);
+        assert_eq!(frames[0].cells.width(), 31);
+        assert_eq!(frames[0].cells.height(), 50);
+        assert_eq!(
+            frames[20]
+                .cells
+                .values()
+                .filter(|g| g.symbol == 'o')
+                .count(),
+            1
+        );
+        assert_eq!(
+            frames[20].caption,
+            "launched at Point(7, 9), step 20: Point(28, -10)"
+        );
+    }
+
+    #[test]
+    fn diagram_points() {
+        let area = Area::new(Point(20, -10), Point(30, -5));
+        let svg = diagram(area).unwrap().to_string();
+
+        assert_eq!(svg.matches("<circle").count(), 21);
+        assert!(svg.contains(r#"<polyline points="0,0 7,-9 13,-17"#));
+    }
+}
@@ -22,7 +22,12 @@
  * SOFTWARE.
  */
 
+use crate::nom::ParseResult;
 use itertools::{Itertools, MinMaxResult};
+use nom::bytes::complete::tag;
+use nom::character::complete::i64;
+use nom::combinator::map;
+use nom::sequence::{preceded, separated_pair};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Point(pub i64, pub i64);
@@ -76,6 +81,21 @@ impl Area {
     }
 }
 
+/// Parses the target area description, e.g. `target area: x=20..30, y=-10..-5`
+pub fn parse_area(input: &str) -> ParseResult<'_, Area> {
+    map(
+        preceded(
+            tag("target area: "),
+            separated_pair(
+                preceded(tag("x="), separated_pair(i64, tag(".."), i64)),
+                tag(", "),
+                preceded(tag("y="), separated_pair(i64, tag(".."), i64)),
+            ),
+        ),
+        |((x0, x1), (y0, y1))| Area::new(Point(x0, y0), Point(x1, y1)),
+    )(input)
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct Probe {
     /// The velocity vector is defined by the point the vector touches starting from the origin.
@@ -22,12 +22,55 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 17 of Advent of Code 2021
+//! Solution for day 17 of Advent of Code 2021
 
+use crate::image::Image;
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::viz::Animation;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
 use itertools::Itertools;
 
+mod animation;
 mod data;
 
+pub const DAY: Day = Day {
+    number: 17,
+    inputs: lazy_input!(17),
+    embedded: embedded_input!(17, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = data::Area;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(data::parse_area, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(*input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two(*input).into())
+    }
+
+    /// Traces the highest launch that hits the area
+    fn draw(&self, input: &Self::Input) -> Option<Image> {
+        animation::diagram(*input).map(Image::Vector)
+    }
+
+    /// Follows the highest launch that hits the area
+    fn animate(&self, input: &Self::Input) -> Option<Box<dyn Animation>> {
+        animation::trajectory(*input).map(|frames| Box::new(frames) as Box<dyn Animation>)
+    }
+}
+
 fn challenge_one(input: data::Area) -> i64 {
     // After trying to solve this challenge with a purely algorithmic approach,
     // and failing to find a solution, I finally gave up and decided to solve it analytically.
@@ -35,10 +78,11 @@ fn challenge_one(input: data::Area) -> i64 {
     // The vertical trajectory of the probe over time is a parabola.
     // The zeros of this parabola are the the origin (the starting point)
     // and double the value of the velocity.
-    // When the velocity of the probe matches the maximum height of the area,
-    // the probe will hit the area exactly at the top. Any faster than that and it will
-    // miss the area skipping from above it to zero.
-    let max_vy = input.max_y().abs() - 1;
+    // When the probe falls back to zero its speed is one more than the launch velocity,
+    // so the next step lands exactly at the bottom of the area when the launch velocity
+    // is one less than its depth. Any faster than that and it will
+    // miss the area skipping from above it to below it.
+    let max_vy = input.min_y().abs() - 1;
 
     let probe = data::Probe::launch(0, max_vy);
 
@@ -58,21 +102,3 @@ fn challenge_two(input: data::Area) -> usize {
         })
         .count()
 }
-
-fn process(name: &str, target_area: data::Area) {
-    println!("Challenge one ({}): {}", name, challenge_one(target_area));
-
-    println!("Challenge two ({}): {}", name, challenge_two(target_area));
-}
-
-fn main() {
-    process(
-        "sample",
-        data::Area::new(data::Point(20, -10), data::Point(30, -5)),
-    );
-
-    process(
-        "input",
-        data::Area::new(data::Point(253, -73), data::Point(280, -46)),
-    );
-}
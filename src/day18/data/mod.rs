@@ -51,9 +51,7 @@ impl Element {
     pub fn magnitude(&self) -> i64 {
         match self {
             &Element::Number(n) => n,
-            Element::Pair(box Pair { left, right }) => {
-                (3 * left.magnitude()) + (2 * right.magnitude())
-            }
+            Element::Pair(pair) => (3 * pair.left.magnitude()) + (2 * pair.right.magnitude()),
         }
     }
 
@@ -74,7 +72,9 @@ impl Element {
     fn explode(&mut self, depth: usize) -> Explosion {
         match self {
             Element::Number(_) => Explosion::Safe,
-            Element::Pair(box Pair { left, right }) => {
+            Element::Pair(pair) => {
+                let Pair { left, right } = pair.as_mut();
+
                 if let (Element::Number(left), Element::Number(right)) = (&left, &right) {
                     if depth >= 4 {
                         let explosion = Explosion::Pair(*left, *right);
@@ -119,8 +119,8 @@ impl Element {
                 Explosion::Handled
             }
 
-            (Element::Pair(box pair), x @ Explosion::Left(_)) => pair.right.handle_explosion(x),
-            (Element::Pair(box pair), x @ Explosion::Right(_)) => pair.left.handle_explosion(x),
+            (Element::Pair(pair), x @ Explosion::Left(_)) => pair.right.handle_explosion(x),
+            (Element::Pair(pair), x @ Explosion::Right(_)) => pair.left.handle_explosion(x),
 
             _ => unreachable!(),
         }
@@ -138,7 +138,7 @@ impl Element {
 
                 true
             }
-            Element::Pair(box Pair { left, right }) => left.split() || right.split(),
+            Element::Pair(pair) => pair.left.split() || pair.right.split(),
             _ => false,
         }
     }
@@ -160,15 +160,12 @@ impl Display for Element {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Element::Number(ref n) => Display::fmt(n, f),
-            Element::Pair(box Pair {
-                ref left,
-                ref right,
-            }) => {
+            Element::Pair(pair) => {
                 f.write_char('[')?;
 
-                Display::fmt(left, f)?;
+                Display::fmt(&pair.left, f)?;
                 f.write_char(',')?;
-                Display::fmt(right, f)?;
+                Display::fmt(&pair.right, f)?;
 
                 f.write_char(']')
             }
@@ -192,7 +189,7 @@ impl Add for Element {
 mod test {
     use test_case::test_case;
 
-    use aoc2021::nom::parse_all;
+    use crate::nom::parse_all;
 
     use super::parsing::parse_element;
     use super::*;
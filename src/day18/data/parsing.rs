@@ -24,11 +24,11 @@
 
 use nom::Parser;
 
-use aoc2021::nom::ParseResult;
+use crate::nom::{lines, ParseResult};
 
 use super::{Element, Pair};
 
-pub fn parse_element(input: &str) -> ParseResult<Element> {
+pub fn parse_element(input: &str) -> ParseResult<'_, Element> {
     nom::sequence::delimited(
         nom::character::complete::space0,
         nom::character::complete::i64
@@ -38,7 +38,7 @@ pub fn parse_element(input: &str) -> ParseResult<Element> {
     )(input)
 }
 
-pub fn parse_pair(input: &str) -> ParseResult<Pair> {
+pub fn parse_pair(input: &str) -> ParseResult<'_, Pair> {
     nom::combinator::map(
         nom::sequence::delimited(
             nom::character::complete::char('['),
@@ -53,15 +53,15 @@ pub fn parse_pair(input: &str) -> ParseResult<Pair> {
     )(input)
 }
 
-pub fn parse_many(input: &str) -> ParseResult<Vec<Element>> {
-    nom::multi::separated_list1(nom::character::complete::line_ending, parse_element)(input)
+pub fn parse_many(input: &str) -> ParseResult<'_, Vec<Element>> {
+    lines(parse_element)(input)
 }
 
 #[cfg(test)]
 mod test {
     use test_case::test_case;
 
-    use aoc2021::nom::parse_all;
+    use crate::nom::parse_all;
 
     use super::*;
 
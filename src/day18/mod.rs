@@ -22,22 +22,45 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 18 of Advent of Code 2021
+//! Solution for day 18 of Advent of Code 2021
 
-#![feature(box_patterns)]
-
-use crate::data::parsing::parse_many;
-use crate::data::Element;
-use anyhow::{anyhow, Context};
-use aoc2021::nom::parse_all;
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+use anyhow::anyhow;
+use data::parsing::parse_many;
+use data::Element;
 use rayon::prelude::*;
 
-static INPUT_DIR: LazyInputProvider = lazy_input!(18);
-
 mod data;
 
+pub const DAY: Day = Day {
+    number: 18,
+    inputs: lazy_input!(18),
+    embedded: embedded_input!(18, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Element>;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(parse_many, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_one(input).map(Answer::from)
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_two(input).map(Answer::from)
+    }
+}
+
 fn challenge_one(input: &[Element]) -> anyhow::Result<i64> {
     // Get the magnitude of the summation of all the elements
     input
@@ -69,31 +92,3 @@ fn challenge_two(input: &[Element]) -> anyhow::Result<i64> {
         .max()
         .ok_or_else(|| anyhow!("Could not compute maximum magnitude"))
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let content = INPUT_DIR
-        .get_input(&format!("{}.txt", name))
-        .context("reading content")
-        .and_then(|content| parse_all(parse_many, &content))?;
-
-    println!(
-        "Challenge one ({}): {}",
-        name,
-        challenge_one(&content).context("challenge one")?
-    );
-
-    println!(
-        "Challenge two ({}): {}",
-        name,
-        challenge_two(&content).context("challenge two")?
-    );
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
@@ -0,0 +1,148 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use crate::nom::{lines, ParseResult};
+use nom::bytes::complete::tag;
+use nom::character::complete::{i32, newline, u32};
+use nom::combinator::{map, opt};
+use nom::multi::{count, separated_list1};
+use nom::sequence::{delimited, terminated, tuple};
+use std::ops::{Add, Sub};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point(pub [i32; 3]);
+
+/// One of the 24 orientations a scanner can face: a permutation of the axes and their signs
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    axes: [usize; 3],
+    signs: [i32; 3],
+}
+
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    pub id: u32,
+    pub beacons: Vec<Point>,
+}
+
+impl Point {
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self - other).0.iter().map(|c| c.abs()).sum()
+    }
+
+    /// Rotation-invariant signature of the vector between two points
+    pub fn fingerprint(self, other: Point) -> [i32; 3] {
+        let mut delta = (self - other).0.map(i32::abs);
+        delta.sort_unstable();
+        delta
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point([
+            self.0[0] + rhs.0[0],
+            self.0[1] + rhs.0[1],
+            self.0[2] + rhs.0[2],
+        ])
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point([
+            self.0[0] - rhs.0[0],
+            self.0[1] - rhs.0[1],
+            self.0[2] - rhs.0[2],
+        ])
+    }
+}
+
+impl Rotation {
+    /// All the proper rotations, those whose determinant is +1
+    pub fn all() -> impl Iterator<Item = Rotation> {
+        const PERMUTATIONS: [([usize; 3], i32); 6] = [
+            ([0, 1, 2], 1),
+            ([0, 2, 1], -1),
+            ([1, 0, 2], -1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([2, 1, 0], -1),
+        ];
+
+        PERMUTATIONS.into_iter().flat_map(|(axes, parity)| {
+            (0..8).filter_map(move |bits| {
+                let signs = [0, 1, 2].map(|i| if bits & (1 << i) == 0 { 1 } else { -1 });
+
+                (signs.iter().product::<i32>() * parity == 1).then_some(Rotation { axes, signs })
+            })
+        })
+    }
+
+    pub fn apply(self, point: Point) -> Point {
+        Point([0, 1, 2].map(|i| self.signs[i] * point.0[self.axes[i]]))
+    }
+}
+
+fn parse_point(input: &str) -> ParseResult<'_, Point> {
+    map(
+        tuple((terminated(i32, tag(",")), terminated(i32, tag(",")), i32)),
+        |(x, y, z)| Point([x, y, z]),
+    )(input)
+}
+
+fn parse_scanner(input: &str) -> ParseResult<'_, Scanner> {
+    map(
+        tuple((
+            delimited(tag("--- scanner "), u32, tag(" ---\n")),
+            lines(parse_point),
+        )),
+        |(id, beacons)| Scanner { id, beacons },
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> ParseResult<'_, Vec<Scanner>> {
+    terminated(
+        separated_list1(count(newline, 2), parse_scanner),
+        opt(newline),
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn rotations_are_distinct() {
+        let point = Point([1, 2, 3]);
+        let rotated: HashSet<_> = Rotation::all().map(|r| r.apply(point)).collect();
+
+        assert_eq!(rotated.len(), 24);
+    }
+}
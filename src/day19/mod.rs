@@ -0,0 +1,275 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 19 of Advent of Code 2021
+
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+use data::{Point, Rotation, Scanner};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+mod data;
+
+pub const DAY: Day = Day {
+    number: 19,
+    inputs: lazy_input!(19),
+    embedded: embedded_input!(19, "answers.toml", "sample.txt"),
+    default_inputs: &["sample"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Scanner>;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(data::parse_input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_one(input).map(Answer::from)
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_two(input).map(Answer::from)
+    }
+}
+
+/// Minimum number of beacons two scanners must share to be considered overlapping
+const OVERLAP: usize = 12;
+
+/// Scanner whose beacons have been indexed by the fingerprint of every pair of them
+struct Survey {
+    beacons: Vec<Point>,
+    pairs: HashMap<[i32; 3], Vec<(usize, usize)>>,
+}
+
+/// Scanner whose position and orientation relative to the first scanner is known
+struct Placed {
+    position: Point,
+    survey: Survey,
+}
+
+impl Survey {
+    fn new(beacons: Vec<Point>) -> Self {
+        let mut pairs: HashMap<_, Vec<_>> = HashMap::new();
+
+        for (i, j) in (0..beacons.len()).tuple_combinations() {
+            pairs
+                .entry(beacons[i].fingerprint(beacons[j]))
+                .or_default()
+                .push((i, j));
+        }
+
+        Self { beacons, pairs }
+    }
+
+    /// Number of fingerprints found in both surveys
+    fn shared_fingerprints(&self, other: &Survey) -> usize {
+        self.pairs
+            .iter()
+            .filter_map(|(fingerprint, pairs)| {
+                other
+                    .pairs
+                    .get(fingerprint)
+                    .map(|other| pairs.len().min(other.len()))
+            })
+            .sum()
+    }
+
+    /// Finds the rotation and position of `other` in the frame of this survey.
+    ///
+    /// Only pairs of beacons with matching fingerprints are used to derive candidate
+    /// rotations, so the rotations are never tried blindly against every beacon.
+    fn align(&self, other: &Survey) -> Option<(Rotation, Point)> {
+        // Overlapping scanners share the fingerprints of every pair among the common beacons
+        if self.shared_fingerprints(other) < OVERLAP * (OVERLAP - 1) / 2 {
+            return None;
+        }
+
+        let known: HashSet<Point> = self.beacons.iter().copied().collect();
+        let mut tried = HashSet::new();
+
+        for (fingerprint, pairs) in &self.pairs {
+            let other_pairs = match other.pairs.get(fingerprint) {
+                Some(other_pairs) => other_pairs,
+                None => continue,
+            };
+
+            for (&(a1, a2), &(b1, b2)) in pairs.iter().cartesian_product(other_pairs) {
+                let (a1, a2) = (self.beacons[a1], self.beacons[a2]);
+
+                for (b1, b2) in [(b1, b2), (b2, b1)] {
+                    let (b1, b2) = (other.beacons[b1], other.beacons[b2]);
+
+                    for rotation in Rotation::all() {
+                        if rotation.apply(b2 - b1) != a2 - a1 {
+                            continue;
+                        }
+
+                        let position = a1 - rotation.apply(b1);
+
+                        if !tried.insert((rotation, position)) {
+                            continue;
+                        }
+
+                        let matching = other
+                            .beacons
+                            .iter()
+                            .filter(|&&beacon| known.contains(&(rotation.apply(beacon) + position)))
+                            .count();
+
+                        if matching >= OVERLAP {
+                            return Some((rotation, position));
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Places every scanner relative to the first one
+fn place_scanners(scanners: &[Scanner]) -> anyhow::Result<Vec<Placed>> {
+    let mut pending: Vec<(u32, Survey)> = scanners
+        .iter()
+        .map(|scanner| (scanner.id, Survey::new(scanner.beacons.clone())))
+        .collect();
+
+    anyhow::ensure!(!pending.is_empty(), "no scanners in the input");
+
+    let mut placed = vec![Placed {
+        position: Point([0, 0, 0]),
+        survey: pending.remove(0).1,
+    }];
+
+    let mut next = 0;
+
+    while next < placed.len() {
+        let mut index = 0;
+
+        while index < pending.len() {
+            match placed[next].survey.align(&pending[index].1) {
+                Some((rotation, position)) => {
+                    let (_, survey) = pending.remove(index);
+
+                    let beacons = survey
+                        .beacons
+                        .iter()
+                        .map(|&beacon| rotation.apply(beacon) + position)
+                        .collect();
+
+                    placed.push(Placed {
+                        position,
+                        survey: Survey::new(beacons),
+                    });
+                }
+                None => index += 1,
+            }
+        }
+
+        next += 1;
+    }
+
+    anyhow::ensure!(
+        pending.is_empty(),
+        "scanners {:?} do not overlap with any other scanner",
+        pending.iter().map(|&(id, _)| id).collect_vec()
+    );
+
+    Ok(placed)
+}
+
+fn challenge_one(input: &[Scanner]) -> anyhow::Result<usize> {
+    let placed = place_scanners(input)?;
+
+    Ok(placed
+        .iter()
+        .flat_map(|scanner| &scanner.survey.beacons)
+        .collect::<HashSet<_>>()
+        .len())
+}
+
+fn challenge_two(input: &[Scanner]) -> anyhow::Result<i32> {
+    let placed = place_scanners(input)?;
+
+    placed
+        .iter()
+        .tuple_combinations()
+        .map(|(a, b)| a.position.manhattan(b.position))
+        .max()
+        .ok_or_else(|| anyhow::anyhow!("at least two scanners are needed"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("input/sample.txt");
+
+    #[test]
+    fn rotated_scanner_is_aligned() {
+        let scanners = parse_all(data::parse_input, SAMPLE).unwrap();
+        let original = Survey::new(scanners[1].beacons.clone());
+
+        for rotation in Rotation::all() {
+            let offset = Point([17, -250, 1000]);
+            let moved = Survey::new(
+                scanners[1]
+                    .beacons
+                    .iter()
+                    .map(|&beacon| rotation.apply(beacon) + offset)
+                    .collect(),
+            );
+
+            let (found, position) = moved.align(&original).unwrap();
+
+            assert_eq!(found, rotation);
+            assert_eq!(position, offset);
+        }
+    }
+
+    #[test]
+    fn sample() {
+        let scanners = parse_all(data::parse_input, SAMPLE).unwrap();
+
+        assert_eq!(challenge_one(&scanners).unwrap(), 79);
+        assert_eq!(challenge_two(&scanners).unwrap(), 3621);
+    }
+
+    #[test]
+    fn trailing_newline() {
+        let input = format!("{}\n", SAMPLE);
+        let scanners = parse_all(data::parse_input, &input).unwrap();
+
+        assert_eq!(scanners.len(), 5);
+        assert_eq!(scanners[4].beacons.last(), Some(&Point([30, -46, -14])));
+    }
+}
@@ -22,17 +22,42 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 2 of Advent of Code 2021
+//! Solution for day 2 of Advent of Code 2021
 
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
 use anyhow::{Context, Error};
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
 use std::str::FromStr;
 
-static INPUT_DIR: LazyInputProvider = lazy_input!(2);
+pub const DAY: Day = Day {
+    number: 2,
+    inputs: lazy_input!(2),
+    embedded: embedded_input!(2, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_input(content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two(input).into())
+    }
+}
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Forward(usize),
     Down(usize),
     Up(usize),
@@ -56,6 +81,10 @@ impl FromStr for Instruction {
     }
 }
 
+fn parse_input(content: &str) -> anyhow::Result<Vec<Instruction>> {
+    content.lines().map(str::parse).collect()
+}
+
 fn challenge_one(input: &[Instruction]) -> usize {
     let (h, v) = input
         .iter()
@@ -79,24 +108,3 @@ fn challenge_two(input: &[Instruction]) -> usize {
 
     h * v
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let content = INPUT_DIR
-        .get_input(&format!("{}.txt", name))
-        .context("reading content")?;
-
-    let input: Vec<Instruction> = content.lines().map(str::parse).collect::<Result<_, _>>()?;
-
-    println!("Challenge one ({}): {}", name, challenge_one(&input));
-
-    println!("Challenge two ({}): {}", name, challenge_two(&input));
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
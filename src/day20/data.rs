@@ -0,0 +1,155 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use crate::grid::Grid;
+use crate::nom::ParseResult;
+use nom::branch::alt;
+use nom::character::complete::{char, newline};
+use nom::combinator::{map, map_res, opt, value};
+use nom::multi::{count, many1, separated_list1};
+use nom::sequence::{separated_pair, terminated};
+use std::fmt::{Debug, Formatter, Write};
+
+/// Lookup table from the 9-bit value of a 3x3 neighbourhood to the resulting pixel
+#[derive(Clone)]
+pub struct Algorithm(Vec<bool>);
+
+/// Image over an infinite plane: a finite window of pixels surrounded by a uniform background
+#[derive(Clone)]
+pub struct Image {
+    pixels: Grid<bool>,
+    background: bool,
+}
+
+#[derive(Clone)]
+pub struct Data {
+    pub algorithm: Algorithm,
+    pub image: Image,
+}
+
+impl Algorithm {
+    pub const SIZE: usize = 512;
+
+    fn lookup(&self, index: usize) -> bool {
+        self.0[index]
+    }
+}
+
+impl Image {
+    /// Number of lit pixels, or `None` if the background is lit and there are infinitely many
+    pub fn lit(&self) -> Option<usize> {
+        (!self.background).then(|| self.pixels.values().filter(|&&pixel| pixel).count())
+    }
+
+    /// Pixel at a position relative to the top left of the window, which may fall outside of it
+    fn pixel(&self, x: isize, y: isize) -> bool {
+        usize::try_from(x)
+            .ok()
+            .zip(usize::try_from(y).ok())
+            .and_then(|position| self.pixels.get(position))
+            .copied()
+            .unwrap_or(self.background)
+    }
+
+    /// Applies the algorithm once, growing the window by one pixel on every side
+    pub fn enhance(&self, algorithm: &Algorithm) -> Image {
+        let mut pixels = Grid::new(self.pixels.width() + 2, self.pixels.height() + 2);
+
+        for (x, y) in pixels.positions().collect::<Vec<_>>() {
+            // The new window starts one pixel before the current one
+            let (cx, cy) = (x as isize - 1, y as isize - 1);
+
+            let index = (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                .fold(0, |index, (dx, dy)| {
+                    (index << 1) | usize::from(self.pixel(cx + dx, cy + dy))
+                });
+
+            pixels[(x, y)] = algorithm.lookup(index);
+        }
+
+        // Every background pixel is surrounded by background, so it maps to either end of the table
+        let background = algorithm.lookup(if self.background {
+            Algorithm::SIZE - 1
+        } else {
+            0
+        });
+
+        Image { pixels, background }
+    }
+
+    /// Iterates over the successive enhancements of the image, starting with the image itself
+    pub fn enhancements<'a>(&self, algorithm: &'a Algorithm) -> impl Iterator<Item = Image> + 'a {
+        std::iter::successors(Some(self.clone()), move |image| {
+            Some(image.enhance(algorithm))
+        })
+    }
+}
+
+impl Debug for Image {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.pixels.rows() {
+            for &pixel in row {
+                f.write_char(if pixel { '#' } else { ' ' })?;
+            }
+            f.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_pixel(input: &str) -> ParseResult<'_, bool> {
+    alt((value(true, char('#')), value(false, char('.'))))(input)
+}
+
+fn parse_algorithm(input: &str) -> ParseResult<'_, Algorithm> {
+    map(count(parse_pixel, Algorithm::SIZE), Algorithm)(input)
+}
+
+fn parse_image(input: &str) -> ParseResult<'_, Image> {
+    map_res(separated_list1(newline, many1(parse_pixel)), |rows| {
+        let width = rows[0].len();
+
+        anyhow::ensure!(
+            rows.iter().all(|row| row.len() == width),
+            "image rows have different widths"
+        );
+
+        Grid::from_cells(width, rows.concat()).map(|pixels| Image {
+            pixels,
+            background: false,
+        })
+    })(input)
+}
+
+pub fn parse_input(input: &str) -> ParseResult<'_, Data> {
+    map(
+        terminated(
+            separated_pair(parse_algorithm, count(newline, 2), parse_image),
+            opt(newline),
+        ),
+        |(algorithm, image)| Data { algorithm, image },
+    )(input)
+}
@@ -0,0 +1,148 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 20 of Advent of Code 2021
+
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+
+mod data;
+
+pub const DAY: Day = Day {
+    number: 20,
+    inputs: lazy_input!(20),
+    embedded: embedded_input!(20, "answers.toml", "sample-1.txt", "sample-2.txt"),
+    default_inputs: &["sample-1", "sample-2"],
+    solution: &Solver {
+        steps_one: 2,
+        steps_two: 50,
+    },
+};
+
+/// Enhances the image a number of times for each challenge and counts the lit pixels
+pub struct Solver {
+    pub steps_one: usize,
+    pub steps_two: usize,
+}
+
+impl Solution for Solver {
+    type Input = data::Data;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(data::parse_input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        lit_after(input, self.steps_one).map(Answer::from)
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        lit_after(input, self.steps_two).map(Answer::from)
+    }
+}
+
+fn lit_after(input: &data::Data, steps: usize) -> anyhow::Result<usize> {
+    let image = input
+        .image
+        .enhancements(&input.algorithm)
+        .nth(steps)
+        .unwrap_or_else(|| unreachable!("enhancements never end"));
+
+    log::debug!("Image after {} steps:\n{:?}", steps, image);
+
+    image
+        .lit()
+        .ok_or_else(|| anyhow::anyhow!("infinitely many pixels are lit after {} steps", steps))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("input/sample-1.txt");
+    const FLIPPING: &str = include_str!("input/sample-2.txt");
+
+    /// Diagonal of 3 lit pixels
+    const DIAGONAL: &str = "#..\n.#.\n..#";
+
+    /// Input whose algorithm computes each pixel from its previous value alone, the center of
+    /// its neighbourhood being the fifth most significant of the 9 bits
+    fn pointwise(pixel: fn(bool) -> bool, image: &str) -> String {
+        let algorithm = (0..data::Algorithm::SIZE)
+            .map(|index| {
+                if pixel(index & 0b10000 != 0) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+
+        format!("{}\n\n{}", algorithm, image)
+    }
+
+    #[test_case(SAMPLE, 2 => 35 ; "sample")]
+    #[test_case(SAMPLE, 50 => 3351 ; "sample long")]
+    fn lit(input: &str, steps: usize) -> usize {
+        let data = parse_all(data::parse_input, input).unwrap();
+
+        lit_after(&data, steps).unwrap()
+    }
+
+    // The identity keeps the image as it is, and inverting twice also gives it back
+    #[test_case(|pixel| pixel, 1 => 3 ; "identity")]
+    #[test_case(|pixel| pixel, 50 => 3 ; "identity long")]
+    #[test_case(|pixel| !pixel, 2 => 3 ; "inversion")]
+    #[test_case(|pixel| !pixel, 50 => 3 ; "inversion long")]
+    fn lit_pointwise(pixel: fn(bool) -> bool, steps: usize) -> usize {
+        let data = parse_all(data::parse_input, &pointwise(pixel, DIAGONAL)).unwrap();
+
+        lit_after(&data, steps).unwrap()
+    }
+
+    #[test]
+    fn flipping_sample_inverts() {
+        assert_eq!(
+            FLIPPING,
+            format!("{}\n", pointwise(|pixel| !pixel, DIAGONAL))
+        );
+    }
+
+    #[test]
+    fn trailing_newline() {
+        let data = parse_all(data::parse_input, &format!("{}\n", SAMPLE)).unwrap();
+
+        assert_eq!(lit_after(&data, 2).unwrap(), 35);
+    }
+
+    #[test]
+    fn infinite_background() {
+        let data = parse_all(data::parse_input, FLIPPING).unwrap();
+
+        assert!(lit_after(&data, 1).is_err());
+    }
+}
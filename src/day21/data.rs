@@ -22,45 +22,33 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 19 of Advent of Code 2021
-
-use anyhow::Context;
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
-
-static INPUT_DIR: LazyInputProvider = lazy_input!(19);
-
-fn challenge_one(_input: &str) -> anyhow::Result<usize> {
-    Ok(0)
+use crate::nom::{parse_usize, ParseResult};
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, newline};
+use nom::combinator::{map, opt};
+use nom::sequence::{delimited, preceded, separated_pair, terminated};
+
+/// Starting positions of both players, counted from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Data {
+    pub positions: [usize; 2],
 }
 
-fn challenge_two(_input: &str) -> anyhow::Result<usize> {
-    Ok(0)
+fn parse_player(input: &str) -> ParseResult<'_, usize> {
+    preceded(
+        delimited(tag("Player "), parse_usize, tag(" starting position:")),
+        preceded(char(' '), parse_usize),
+    )(input)
 }
 
-fn process(name: &str) -> anyhow::Result<()> {
-    let content = INPUT_DIR
-        .get_input(&format!("{}.txt", name))
-        .context("reading content")?;
-
-    println!(
-        "Challenge one ({}): {}",
-        name,
-        challenge_one(&content).context("challenge one")?
-    );
-
-    println!(
-        "Challenge two ({}): {}",
-        name,
-        challenge_two(&content).context("challenge two")?
-    );
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
+pub fn parse_input(input: &str) -> ParseResult<'_, Data> {
+    map(
+        terminated(
+            separated_pair(parse_player, newline, parse_player),
+            opt(newline),
+        ),
+        |(one, two)| Data {
+            positions: [one, two],
+        },
+    )(input)
 }
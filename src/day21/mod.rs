@@ -0,0 +1,297 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 21 of Advent of Code 2021
+
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+use anyhow::Context;
+use rustc_hash::FxHashMap;
+
+mod data;
+
+pub const DAY: Day = Day {
+    number: 21,
+    inputs: lazy_input!(21),
+    embedded: embedded_input!(21, "answers.toml", "sample.txt"),
+    default_inputs: &["sample"],
+    solution: &Solver {
+        practice: Rules {
+            board: 10,
+            target: 1000,
+            faces: 100,
+        },
+        dirac: Rules {
+            board: 10,
+            target: 21,
+            faces: 3,
+        },
+    },
+};
+
+/// Number of times the die is rolled on each turn
+const ROLLS_PER_TURN: usize = 3;
+
+/// Parameters of a game of Dirac Dice
+#[derive(Debug, Clone, Copy)]
+pub struct Rules {
+    /// Number of spaces on the circular board
+    pub board: usize,
+    /// Score a player must reach to win
+    pub target: usize,
+    /// Number of faces on the die
+    pub faces: usize,
+}
+
+/// Plays the practice game with a deterministic die and the real game with a quantum die
+pub struct Solver {
+    pub practice: Rules,
+    pub dirac: Rules,
+}
+
+impl Solution for Solver {
+    type Input = data::Data;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(data::parse_input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        deterministic_game(&self.practice, input).map(Answer::from)
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        let wins = quantum_game(&self.dirac, input)?;
+        let most = wins.into_iter().max().unwrap_or_default();
+
+        Ok(i128::try_from(most)
+            .context("the number of universes does not fit in an answer")?
+            .into())
+    }
+}
+
+impl Rules {
+    fn validate(&self, input: &data::Data) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.board > 0 && self.faces > 0,
+            "board and die must not be empty"
+        );
+
+        for position in input.positions {
+            anyhow::ensure!(
+                (1..=self.board).contains(&position),
+                "starting position {} is not on a board of size {}",
+                position,
+                self.board
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Position reached by moving a number of spaces, wrapping around the board
+    fn advance(&self, position: usize, spaces: usize) -> usize {
+        (position - 1 + spaces) % self.board + 1
+    }
+
+    /// Distinct sums of a turn's rolls along with the number of universes producing each of them
+    fn roll_distribution(&self) -> Vec<(usize, u64)> {
+        let mut counts = vec![0; ROLLS_PER_TURN * self.faces + 1];
+        counts[0] = 1;
+
+        for _ in 0..ROLLS_PER_TURN {
+            let mut next = vec![0; counts.len()];
+
+            for (sum, &count) in counts.iter().enumerate().filter(|(_, &count)| count > 0) {
+                for face in 1..=self.faces.min(counts.len() - 1 - sum) {
+                    next[sum + face] += count;
+                }
+            }
+
+            counts = next;
+        }
+
+        counts
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, count)| count > 0)
+            .collect()
+    }
+}
+
+/// Plays with a die rolling 1, 2, 3... in order and returns the losing score times the number of rolls
+fn deterministic_game(rules: &Rules, input: &data::Data) -> anyhow::Result<usize> {
+    rules.validate(input)?;
+
+    let mut positions = input.positions;
+    let mut scores = [0; 2];
+    let mut die = (1..=rules.faces).cycle();
+    let mut rolls = 0;
+
+    for player in (0..2).cycle() {
+        let spaces: usize = die.by_ref().take(ROLLS_PER_TURN).sum();
+        rolls += ROLLS_PER_TURN;
+
+        positions[player] = rules.advance(positions[player], spaces);
+        scores[player] += positions[player];
+
+        if scores[player] >= rules.target {
+            return Ok(scores[1 - player] * rolls);
+        }
+    }
+
+    unreachable!("The game only ends when a player wins")
+}
+
+/// Counts the universes in which each player wins when every roll splits the universe
+///
+/// Universes are tracked turn by turn, merging the ones in the same state. A state can only be
+/// reached on a single turn, as every turn raises the score of a player, so each turn only needs
+/// the states of the previous one.
+///
+/// # Errors
+/// An error is returned if the rules do not fit the input, or if a number of universes does not
+/// fit in a u128
+fn quantum_game(rules: &Rules, input: &data::Data) -> anyhow::Result<[u128; 2]> {
+    rules.validate(input)?;
+
+    let distribution = rules.roll_distribution();
+    let overflow = || anyhow::anyhow!("the number of universes does not fit in a u128");
+
+    let mut wins = [0_u128; 2];
+    let mut universes = FxHashMap::default();
+    universes.insert((input.positions, [0; 2]), 1_u128);
+
+    for player in (0..2).cycle() {
+        if universes.is_empty() {
+            break;
+        }
+
+        let mut next = FxHashMap::default();
+
+        for ((positions, scores), count) in universes {
+            for &(spaces, rolls) in &distribution {
+                let count = count.checked_mul(u128::from(rolls)).ok_or_else(overflow)?;
+
+                let mut positions = positions;
+                let mut scores = scores;
+
+                positions[player] = rules.advance(positions[player], spaces);
+                scores[player] += positions[player];
+
+                let total = if scores[player] >= rules.target {
+                    &mut wins[player]
+                } else {
+                    next.entry((positions, scores)).or_insert(0)
+                };
+
+                *total = total.checked_add(count).ok_or_else(overflow)?;
+            }
+        }
+
+        universes = next;
+    }
+
+    Ok(wins)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: data::Data = data::Data { positions: [4, 8] };
+
+    const PRACTICE: Rules = Rules {
+        board: 10,
+        target: 1000,
+        faces: 100,
+    };
+
+    const DIRAC: Rules = Rules {
+        board: 10,
+        target: 21,
+        faces: 3,
+    };
+
+    #[test]
+    fn roll_distribution() {
+        assert_eq!(
+            DIRAC.roll_distribution(),
+            vec![(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)]
+        );
+    }
+
+    #[test_case(PRACTICE => 739785 ; "puzzle rules")]
+    #[test_case(Rules { target: 20, ..PRACTICE } => 135 ; "short game")]
+    #[test_case(Rules { faces: 6, ..PRACTICE } => 898665 ; "six sided die")]
+    fn deterministic(rules: Rules) -> usize {
+        deterministic_game(&rules, &SAMPLE).unwrap()
+    }
+
+    #[test_case(DIRAC => [444356092776315, 341960390180808] ; "puzzle rules")]
+    #[test_case(Rules { target: 1, ..DIRAC } => [27, 0] ; "first move wins")]
+    fn quantum(rules: Rules) -> [u128; 2] {
+        quantum_game(&rules, &SAMPLE).unwrap()
+    }
+
+    /// On a single space board each turn scores 1, so every game lasts the same number of turns
+    const SINGLE_SPACE: Rules = Rules {
+        board: 1,
+        target: 13,
+        faces: 3,
+    };
+
+    #[test]
+    fn wins_wider_than_u64() {
+        let start = data::Data { positions: [1, 1] };
+
+        // The first player wins on the 25th turn, in every one of the 27^25 universes
+        assert_eq!(
+            quantum_game(&SINGLE_SPACE, &start).unwrap(),
+            [3_u128.pow(75), 0]
+        );
+    }
+
+    #[test]
+    fn wins_overflow() {
+        let start = data::Data { positions: [1, 1] };
+        let rules = Rules {
+            target: 14,
+            ..SINGLE_SPACE
+        };
+
+        // 27^27 universes are more than a u128 can count
+        assert!(quantum_game(&rules, &start).is_err());
+    }
+
+    #[test]
+    fn position_off_board() {
+        let rules = Rules { board: 6, ..DIRAC };
+
+        assert!(quantum_game(&rules, &SAMPLE).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use crate::nom::{lines, ParseResult};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, i64 as parse_i64, newline};
+use nom::combinator::{map, opt, value, verify};
+use nom::error::context;
+use nom::sequence::{preceded, separated_pair, terminated, tuple};
+
+/// Axis aligned box of cubes with inclusive bounds on every axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cuboid {
+    pub min: [i64; 3],
+    pub max: [i64; 3],
+}
+
+/// Single reboot step turning every cube in a cuboid on or off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub on: bool,
+    pub cuboid: Cuboid,
+}
+
+impl Cuboid {
+    /// Cuboid spanning `-radius..=radius` on every axis
+    pub fn centered(radius: i64) -> Self {
+        Self {
+            min: [-radius; 3],
+            max: [radius; 3],
+        }
+    }
+
+    /// Cuboid of the cubes contained in both cuboids, if there are any
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let mut result = *self;
+
+        for axis in 0..3 {
+            result.min[axis] = self.min[axis].max(other.min[axis]);
+            result.max[axis] = self.max[axis].min(other.max[axis]);
+
+            if result.min[axis] > result.max[axis] {
+                return None;
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Number of cubes in the cuboid, if it fits in an i128
+    pub fn volume(&self) -> Option<i128> {
+        (0..3)
+            .map(|axis| i128::from(self.max[axis]) - i128::from(self.min[axis]) + 1)
+            .try_fold(1_i128, i128::checked_mul)
+    }
+}
+
+fn parse_range(input: &str) -> ParseResult<'_, (i64, i64)> {
+    verify(
+        separated_pair(parse_i64, tag(".."), parse_i64),
+        |(min, max)| min <= max,
+    )(input)
+}
+
+fn parse_cuboid(input: &str) -> ParseResult<'_, Cuboid> {
+    map(
+        tuple((
+            preceded(tag("x="), parse_range),
+            preceded(tag(",y="), parse_range),
+            preceded(tag(",z="), parse_range),
+        )),
+        |((x0, x1), (y0, y1), (z0, z1))| Cuboid {
+            min: [x0, y0, z0],
+            max: [x1, y1, z1],
+        },
+    )(input)
+}
+
+fn parse_step(input: &str) -> ParseResult<'_, Step> {
+    context(
+        "step",
+        map(
+            separated_pair(
+                alt((value(true, tag("on")), value(false, tag("off")))),
+                char(' '),
+                context("cuboid", parse_cuboid),
+            ),
+            |(on, cuboid)| Step { on, cuboid },
+        ),
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> ParseResult<'_, Vec<Step>> {
+    terminated(lines(parse_step), opt(newline))(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::nom::parse_all;
+    use test_case::test_case;
+
+    const fn cuboid(min: [i64; 3], max: [i64; 3]) -> Cuboid {
+        Cuboid { min, max }
+    }
+
+    #[test]
+    fn parse_negative_coordinates() {
+        let steps = parse_all(parse_input, "off x=-54112..-39298,y=-85059..-49293,z=5..7").unwrap();
+
+        assert_eq!(
+            steps,
+            vec![Step {
+                on: false,
+                cuboid: cuboid([-54112, -85059, 5], [-39298, -49293, 7]),
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_reversed_range() {
+        assert!(parse_all(parse_input, "on x=3..1,y=0..0,z=0..0").is_err());
+    }
+
+    #[test_case(cuboid([0; 3], [2; 3]), cuboid([1; 3], [3; 3]) => Some(cuboid([1; 3], [2; 3])) ; "overlapping")]
+    #[test_case(cuboid([0; 3], [2; 3]), cuboid([2, 0, 0], [4, 0, 0]) => Some(cuboid([2, 0, 0], [2, 0, 0])) ; "touching")]
+    #[test_case(cuboid([0; 3], [2; 3]), cuboid([0, 3, 0], [2, 5, 2]) => None ; "disjoint")]
+    fn intersection(a: Cuboid, b: Cuboid) -> Option<Cuboid> {
+        a.intersection(&b)
+    }
+
+    #[test_case(cuboid([-1; 3], [1; 3]) => Some(27) ; "small")]
+    #[test_case(cuboid([i64::MIN, 0, 0], [i64::MAX, 0, 0]) => Some(1 << 64) ; "wider than i64")]
+    #[test_case(cuboid([i64::MIN; 3], [i64::MAX; 3]) => None ; "wider than i128")]
+    fn volume(cuboid: Cuboid) -> Option<i128> {
+        cuboid.volume()
+    }
+}
@@ -0,0 +1,220 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 22 of Advent of Code 2021
+
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+use anyhow::Context;
+use data::{Cuboid, Step};
+
+mod data;
+
+pub const DAY: Day = Day {
+    number: 22,
+    inputs: lazy_input!(22),
+    embedded: embedded_input!(
+        22,
+        "answers.toml",
+        "sample-1.txt",
+        "sample-2.txt",
+        "sample-3.txt"
+    ),
+    default_inputs: &["sample-1", "sample-2", "sample-3"],
+    solution: &Solver,
+};
+
+/// Half the side of the region affected by the initialization procedure
+const INITIALIZATION_RADIUS: i64 = 50;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Step>;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(data::parse_input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        let region = Cuboid::centered(INITIALIZATION_RADIUS);
+
+        Ok(Reactor::reboot(input, Some(&region)).lit()?.into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(Reactor::reboot(input, None).lit()?.into())
+    }
+}
+
+/// Lit cubes as a signed sum of cuboids, following the inclusion–exclusion principle
+///
+/// Every cuboid added on top of existing ones is compensated by adding their intersections with
+/// the opposite sign, so overlapping cubes are only ever counted once.
+#[derive(Debug, Default)]
+struct Reactor {
+    cuboids: Vec<(Cuboid, i64)>,
+}
+
+impl Reactor {
+    /// Runs the reboot steps, ignoring any cube outside of the region if one is given
+    fn reboot(steps: &[Step], region: Option<&Cuboid>) -> Self {
+        let mut reactor = Self::default();
+
+        for step in steps {
+            let cuboid = match region {
+                Some(region) => step.cuboid.intersection(region),
+                None => Some(step.cuboid),
+            };
+
+            if let Some(cuboid) = cuboid {
+                reactor.apply(step.on, cuboid);
+            }
+        }
+
+        reactor
+    }
+
+    fn apply(&mut self, on: bool, cuboid: Cuboid) {
+        let overlaps: Vec<_> = self
+            .cuboids
+            .iter()
+            .filter_map(|(existing, sign)| {
+                existing
+                    .intersection(&cuboid)
+                    .map(|overlap| (overlap, -sign))
+            })
+            .collect();
+
+        self.cuboids.extend(overlaps);
+
+        if on {
+            self.cuboids.push((cuboid, 1));
+        }
+    }
+
+    /// Number of lit cubes
+    ///
+    /// # Errors
+    /// An error is returned if the count, or any volume it adds up, does not fit in an i128
+    fn lit(&self) -> anyhow::Result<i128> {
+        self.cuboids
+            .iter()
+            .try_fold(0_i128, |total, (cuboid, sign)| {
+                cuboid
+                    .volume()
+                    .and_then(|volume| volume.checked_mul(i128::from(*sign)))
+                    .and_then(|volume| total.checked_add(volume))
+            })
+            .context("the number of lit cubes does not fit in an i128")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("input/sample-1.txt");
+
+    #[test_case(1 => 27 ; "single cuboid")]
+    #[test_case(2 => 46 ; "overlapping cuboids")]
+    #[test_case(3 => 38 ; "turned off")]
+    #[test_case(4 => 39 ; "turned back on")]
+    fn lit_after_steps(steps: usize) -> i128 {
+        let input = parse_all(data::parse_input, SAMPLE).unwrap();
+
+        Reactor::reboot(&input[..steps], None).lit().unwrap()
+    }
+
+    /// Lit cubes of the initialization region, switching each of its cubes one by one
+    fn brute_force(steps: &[Step]) -> i128 {
+        let side = 2 * INITIALIZATION_RADIUS + 1;
+        let mut cubes = vec![false; (side * side * side) as usize];
+
+        for step in steps {
+            let Some(cuboid) = step
+                .cuboid
+                .intersection(&Cuboid::centered(INITIALIZATION_RADIUS))
+            else {
+                continue;
+            };
+
+            for x in cuboid.min[0]..=cuboid.max[0] {
+                for y in cuboid.min[1]..=cuboid.max[1] {
+                    for z in cuboid.min[2]..=cuboid.max[2] {
+                        let [x, y, z] = [x, y, z].map(|c| c + INITIALIZATION_RADIUS);
+                        cubes[((x * side + y) * side + z) as usize] = step.on;
+                    }
+                }
+            }
+        }
+
+        cubes.iter().filter(|&&on| on).count() as i128
+    }
+
+    #[test_case(include_str!("input/sample-2.txt") => 590784 ; "larger example")]
+    #[test_case(include_str!("input/sample-3.txt") => 474140 ; "reboot example")]
+    fn initialization_brute_force(sample: &str) -> i128 {
+        let input = parse_all(data::parse_input, sample).unwrap();
+        let region = Cuboid::centered(INITIALIZATION_RADIUS);
+        let expected = brute_force(&input);
+
+        assert_eq!(
+            Reactor::reboot(&input, Some(&region)).lit().unwrap(),
+            expected
+        );
+        expected
+    }
+
+    #[test]
+    fn volume_overflow() {
+        let input = parse_all(
+            data::parse_input,
+            "on x=-9223372036854775808..9223372036854775807,y=0..4294967296,z=0..4294967296",
+        )
+        .unwrap();
+
+        assert!(Reactor::reboot(&input, None).lit().is_err());
+    }
+
+    #[test]
+    fn outside_region() {
+        let input = parse_all(
+            data::parse_input,
+            "on x=-60..-51,y=0..0,z=0..0\non x=50..51,y=0..0,z=0..0",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Reactor::reboot(&input, Some(&Cuboid::centered(50)))
+                .lit()
+                .unwrap(),
+            1
+        );
+        assert_eq!(Reactor::reboot(&input, None).lit().unwrap(), 12);
+    }
+}
@@ -0,0 +1,474 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use crate::grid::Grid;
+use crate::nom::ParseResult;
+use crate::viz::{Color, Frame, Glyph};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, newline};
+use nom::combinator::{map, map_res, opt, value};
+use nom::error::context;
+use nom::multi::{count, many1};
+use nom::sequence::{delimited, pair, terminated, tuple};
+use std::fmt::{Display, Formatter, Write};
+
+/// Number of cells in the hallway
+pub const HALLWAY: usize = 11;
+
+/// Number of side rooms, one for each kind of amphipod
+pub const ROOMS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+impl Amphipod {
+    const ALL: [Amphipod; ROOMS] = [Self::Amber, Self::Bronze, Self::Copper, Self::Desert];
+
+    /// Side room this amphipod must end up in
+    pub fn room(self) -> usize {
+        self as usize
+    }
+
+    /// Energy spent to move a single step
+    pub fn energy(self) -> usize {
+        10usize.pow(self as u32)
+    }
+
+    fn symbol(self) -> char {
+        (b'A' + self as u8) as char
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Amber => Color::Yellow,
+            Self::Bronze => Color::LightRed,
+            Self::Copper => Color::LightCyan,
+            Self::Desert => Color::LightMagenta,
+        }
+    }
+}
+
+/// Cell of the burrow where an amphipod can be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Hallway(usize),
+    /// Side room and slot within it, starting from the one next to the hallway
+    Room(usize, usize),
+}
+
+/// Amphipod moving between two locations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub amphipod: Amphipod,
+    pub from: Location,
+    pub to: Location,
+    pub energy: usize,
+}
+
+/// Burrow with side rooms holding `DEPTH` amphipods each
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Burrow<const DEPTH: usize> {
+    hallway: [Option<Amphipod>; HALLWAY],
+    rooms: [[Option<Amphipod>; DEPTH]; ROOMS],
+}
+
+/// Hallway cell right outside a side room, where amphipods may never stop
+fn entrance(room: usize) -> usize {
+    2 + 2 * room
+}
+
+fn is_entrance(cell: usize) -> bool {
+    (0..ROOMS).any(|room| entrance(room) == cell)
+}
+
+impl<const DEPTH: usize> Burrow<DEPTH> {
+    /// Burrow where every amphipod is in its own room
+    pub fn organized() -> Self {
+        Self {
+            hallway: [None; HALLWAY],
+            rooms: Amphipod::ALL.map(|amphipod| [Some(amphipod); DEPTH]),
+        }
+    }
+
+    pub fn is_organized(&self) -> bool {
+        *self == Self::organized()
+    }
+
+    /// Whether a room only holds amphipods that belong there, so others may enter it
+    fn is_clean(&self, room: usize) -> bool {
+        self.rooms[room]
+            .iter()
+            .flatten()
+            .all(|amphipod| amphipod.room() == room)
+    }
+
+    /// Whether the hallway is empty between two cells, excluding the first one
+    fn is_clear(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+
+        self.hallway[range].iter().all(Option::is_none)
+    }
+
+    /// Every legal move from this burrow
+    pub fn moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+
+        // Amphipods in the hallway may only move into their own room, once it is clean
+        for (cell, amphipod) in self.hallway.iter().enumerate() {
+            let amphipod = match amphipod {
+                Some(amphipod) => *amphipod,
+                None => continue,
+            };
+
+            let room = amphipod.room();
+
+            if !self.is_clean(room) || !self.is_clear(cell, entrance(room)) {
+                continue;
+            }
+
+            // Rooms fill from the bottom, so the free slot is the one right above the occupants
+            let occupied = self.rooms[room]
+                .iter()
+                .position(Option::is_some)
+                .unwrap_or(DEPTH);
+
+            if let Some(slot) = occupied.checked_sub(1) {
+                moves.push(Move {
+                    amphipod,
+                    from: Location::Hallway(cell),
+                    to: Location::Room(room, slot),
+                    energy: (cell.abs_diff(entrance(room)) + slot + 1) * amphipod.energy(),
+                });
+            }
+        }
+
+        // Amphipods leave rooms holding strangers to any reachable cell that is not an entrance
+        for room in 0..ROOMS {
+            if self.is_clean(room) {
+                continue;
+            }
+
+            let (slot, amphipod) = match self.rooms[room]
+                .iter()
+                .enumerate()
+                .find_map(|(slot, amphipod)| amphipod.map(|amphipod| (slot, amphipod)))
+            {
+                Some(top) => top,
+                None => continue,
+            };
+
+            for cell in (0..HALLWAY).filter(|&cell| !is_entrance(cell)) {
+                if self.is_clear(entrance(room), cell) {
+                    moves.push(Move {
+                        amphipod,
+                        from: Location::Room(room, slot),
+                        to: Location::Hallway(cell),
+                        energy: (cell.abs_diff(entrance(room)) + slot + 1) * amphipod.energy(),
+                    });
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn cell_mut(&mut self, location: Location) -> &mut Option<Amphipod> {
+        match location {
+            Location::Hallway(cell) => &mut self.hallway[cell],
+            Location::Room(room, slot) => &mut self.rooms[room][slot],
+        }
+    }
+
+    /// Burrow after a move is made
+    pub fn apply(&self, step: &Move) -> Self {
+        let mut next = *self;
+
+        *next.cell_mut(step.from) = None;
+        *next.cell_mut(step.to) = Some(step.amphipod);
+
+        next
+    }
+
+    /// Lower bound on the energy needed to organize the burrow, ignoring any blocking
+    pub fn minimum_energy(&self) -> usize {
+        let in_hallway = self
+            .hallway
+            .iter()
+            .enumerate()
+            .filter_map(|(cell, amphipod)| amphipod.map(|amphipod| (cell, 0, amphipod)));
+
+        // Amphipods already in their room with only their own kind below them never move again
+        let in_rooms = (0..ROOMS).flat_map(|room| {
+            let column = &self.rooms[room];
+
+            (0..DEPTH).filter_map(move |slot| {
+                let amphipod = column[slot]?;
+                let settled = column[slot..]
+                    .iter()
+                    .all(|other| other.map(Amphipod::room) == Some(room));
+
+                (!settled).then(|| (entrance(room), slot + 1, amphipod))
+            })
+        });
+
+        in_hallway
+            .chain(in_rooms)
+            .map(|(cell, exit, amphipod)| {
+                let target = entrance(amphipod.room());
+                // Leaving a room to enter it again requires stepping aside
+                let horizontal = if exit > 0 && cell == target {
+                    2
+                } else {
+                    cell.abs_diff(target)
+                };
+
+                (exit + horizontal + 1) * amphipod.energy()
+            })
+            .sum()
+    }
+
+    /// Inserts rows of amphipods between the first and the remaining rows of every room
+    pub fn unfold<const UNFOLDED: usize>(&self, rows: &[[Amphipod; ROOMS]]) -> Burrow<UNFOLDED> {
+        assert_eq!(DEPTH + rows.len(), UNFOLDED, "unfolded depth must add up");
+
+        let mut rooms = [[None; UNFOLDED]; ROOMS];
+
+        for (room, column) in rooms.iter_mut().enumerate() {
+            let inserted = rows.iter().map(|row| Some(row[room]));
+            let original = self.rooms[room].iter().copied();
+
+            for (cell, amphipod) in column.iter_mut().zip(
+                original
+                    .clone()
+                    .take(1)
+                    .chain(inserted)
+                    .chain(original.skip(1)),
+            ) {
+                *cell = amphipod;
+            }
+        }
+
+        Burrow {
+            hallway: self.hallway,
+            rooms,
+        }
+    }
+
+    /// Picture of the burrow, drawn like its diagram
+    pub fn frame(&self, caption: String) -> Frame {
+        let wall = Glyph::new(' ', Color::Reset);
+        let mut cells = Grid::filled(HALLWAY + 2, DEPTH + 3, wall);
+
+        for (y, line) in self.to_string().lines().enumerate() {
+            for (x, symbol) in line.chars().enumerate() {
+                let color = match symbol {
+                    '#' => Color::DarkGray,
+                    '.' => Color::Gray,
+                    _ => Amphipod::ALL
+                        .into_iter()
+                        .find(|amphipod| amphipod.symbol() == symbol)
+                        .map_or(Color::Reset, Amphipod::color),
+                };
+
+                cells[(x, y)] = Glyph::new(symbol, color);
+            }
+        }
+
+        Frame { caption, cells }
+    }
+}
+
+impl<const DEPTH: usize> Display for Burrow<DEPTH> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let symbol = |cell: &Option<Amphipod>| cell.map_or('.', Amphipod::symbol);
+
+        writeln!(f, "{}", "#".repeat(HALLWAY + 2))?;
+
+        f.write_char('#')?;
+        for cell in &self.hallway {
+            f.write_char(symbol(cell))?;
+        }
+        writeln!(f, "#")?;
+
+        for slot in 0..DEPTH {
+            f.write_str(if slot == 0 { "###" } else { "  #" })?;
+            for room in &self.rooms {
+                write!(f, "{}#", symbol(&room[slot]))?;
+            }
+            writeln!(f, "{}", if slot == 0 { "##" } else { "" })?;
+        }
+
+        write!(f, "  {}", "#".repeat(2 * ROOMS + 1))
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Hallway(cell) => write!(f, "hallway {}", cell),
+            Location::Room(room, slot) => write!(f, "room {} slot {}", room, slot),
+        }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} moves from {} to {} using {} energy",
+            self.amphipod.symbol(),
+            self.from,
+            self.to,
+            self.energy
+        )
+    }
+}
+
+fn parse_cell(input: &str) -> ParseResult<'_, Option<Amphipod>> {
+    alt((
+        value(None, char('.')),
+        value(Some(Amphipod::Amber), char('A')),
+        value(Some(Amphipod::Bronze), char('B')),
+        value(Some(Amphipod::Copper), char('C')),
+        value(Some(Amphipod::Desert), char('D')),
+    ))(input)
+}
+
+fn parse_room_row(input: &str) -> ParseResult<'_, [Option<Amphipod>; ROOMS]> {
+    map(
+        delimited(
+            alt((tag("###"), tag("  #"))),
+            tuple((
+                terminated(parse_cell, char('#')),
+                terminated(parse_cell, char('#')),
+                terminated(parse_cell, char('#')),
+                parse_cell,
+            )),
+            pair(char('#'), opt(tag("##"))),
+        ),
+        |(a, b, c, d)| [a, b, c, d],
+    )(input)
+}
+
+pub fn parse_input<const DEPTH: usize>(input: &str) -> ParseResult<'_, Burrow<DEPTH>> {
+    map_res(
+        tuple((
+            terminated(tag("#############"), newline),
+            context(
+                "hallway",
+                delimited(
+                    char('#'),
+                    count(parse_cell, HALLWAY),
+                    pair(char('#'), newline),
+                ),
+            ),
+            context("rooms", many1(terminated(parse_room_row, newline))),
+            terminated(tag("  #########"), opt(newline)),
+        )),
+        |(_, hallway, rows, _)| {
+            anyhow::ensure!(
+                rows.len() == DEPTH,
+                "expected rooms {} deep, found {}",
+                DEPTH,
+                rows.len()
+            );
+
+            let mut burrow = Burrow {
+                hallway: [None; HALLWAY],
+                rooms: [[None; DEPTH]; ROOMS],
+            };
+
+            burrow.hallway.copy_from_slice(&hallway);
+
+            for (slot, row) in rows.into_iter().enumerate() {
+                for (room, amphipod) in row.into_iter().enumerate() {
+                    burrow.rooms[room][slot] = amphipod;
+                }
+            }
+
+            Ok(burrow)
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::nom::parse_all;
+
+    const SAMPLE: &str = include_str!("input/sample.txt");
+
+    #[test]
+    fn display_round_trip() {
+        let burrow: Burrow<2> = parse_all(parse_input, SAMPLE).unwrap();
+
+        assert_eq!(burrow.to_string(), SAMPLE.trim_end());
+    }
+
+    #[test]
+    fn wrong_depth() {
+        assert!(parse_all(parse_input::<4>, SAMPLE).is_err());
+    }
+
+    #[test]
+    fn room_with_gap() {
+        let burrow: Burrow<2> = parse_all(
+            parse_input,
+            "#############\n#A..........#\n###A#B#C#D###\n  #.#B#C#D#\n  #########",
+        )
+        .unwrap();
+
+        // Reaching the empty slot would mean passing through the amber amphipod above it
+        assert!(burrow.moves().is_empty());
+    }
+
+    #[test]
+    fn blocked_hallway() {
+        let burrow: Burrow<2> = parse_all(
+            parse_input,
+            "#############\n#.....D.....#\n###B#C#B#.###\n  #A#D#C#A#\n  #########",
+        )
+        .unwrap();
+
+        // No amphipod gets past the one in the hallway, which cannot enter its room yet
+        assert!(burrow
+            .moves()
+            .iter()
+            .all(|step| match (step.from, step.to) {
+                (Location::Room(room, _), Location::Hallway(cell)) =>
+                    (cell < 5) == (entrance(room) < 5),
+                _ => false,
+            }));
+    }
+}
@@ -0,0 +1,217 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 23 of Advent of Code 2021
+
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::search::astar;
+use crate::viz::{Animation, Frame};
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+use data::{Amphipod, Burrow, Move};
+
+mod data;
+
+pub const DAY: Day = Day {
+    number: 23,
+    inputs: lazy_input!(23),
+    embedded: embedded_input!(23, "answers.toml", "sample.txt"),
+    default_inputs: &["sample"],
+    solution: &Solver,
+};
+
+/// Rows revealed when unfolding the diagram for the second challenge
+const FOLDED_ROWS: [[Amphipod; data::ROOMS]; 2] = {
+    use Amphipod::*;
+
+    [
+        [Desert, Copper, Bronze, Amber],
+        [Desert, Bronze, Amber, Copper],
+    ]
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Burrow<2>;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(data::parse_input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        organize(input).map(|(energy, _)| energy.into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        let unfolded: Burrow<4> = input.unfold(&FOLDED_ROWS);
+
+        organize(&unfolded).map(|(energy, _)| energy.into())
+    }
+
+    /// Plays the cheapest sequence of moves for both challenges
+    fn animate(&self, input: &Self::Input) -> Option<Box<dyn Animation>> {
+        let folded = replay(input, "folded").ok()?;
+        let unfolded: Burrow<4> = input.unfold(&FOLDED_ROWS);
+        // Organizing the unfolded burrow takes a while, so only do it once the first part is over
+        let unfolded = std::iter::once(unfolded)
+            .flat_map(|burrow| replay(&burrow, "unfolded").into_iter().flatten());
+
+        Some(Box::new(folded.into_iter().chain(unfolded)))
+    }
+}
+
+/// Frames of the burrow after each move organizing it
+fn replay<const DEPTH: usize>(burrow: &Burrow<DEPTH>, label: &str) -> anyhow::Result<Vec<Frame>> {
+    let (_, moves) = organize(burrow)?;
+
+    let initial = burrow.frame(format!("{}: initial diagram", label));
+    let mut current = *burrow;
+    let mut spent = 0;
+
+    let steps = moves.iter().map(|step| {
+        current = current.apply(step);
+        spent += step.energy;
+
+        current.frame(format!("{}: {}, {} total", label, step, spent))
+    });
+
+    Ok(std::iter::once(initial).chain(steps).collect())
+}
+
+/// Finds the sequence of moves organizing the burrow with the least energy
+fn organize<const DEPTH: usize>(burrow: &Burrow<DEPTH>) -> anyhow::Result<(usize, Vec<Move>)> {
+    let path = astar(
+        *burrow,
+        |burrow| {
+            burrow
+                .moves()
+                .into_iter()
+                .map(|step| (burrow.apply(&step), step.energy))
+                .collect::<Vec<_>>()
+        },
+        Burrow::minimum_energy,
+        Burrow::is_organized,
+    )
+    .ok_or_else(|| anyhow::anyhow!("The amphipods cannot be organized"))?;
+
+    // The search only keeps the burrows, so recover the move leading to each of them
+    let moves: Vec<_> = path
+        .nodes
+        .windows(2)
+        .map(|pair| {
+            pair[0]
+                .moves()
+                .into_iter()
+                .filter(|step| pair[0].apply(step) == pair[1])
+                .min_by_key(|step| step.energy)
+                .expect("consecutive burrows are one move apart")
+        })
+        .collect();
+
+    Ok((path.cost, moves))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("input/sample.txt");
+
+    #[test]
+    fn already_organized() {
+        let (energy, moves) = organize(&Burrow::<2>::organized()).unwrap();
+
+        assert_eq!(energy, 0);
+        assert!(moves.is_empty());
+    }
+
+    #[test]
+    fn moves_add_up() {
+        let burrow: Burrow<2> = parse_all(data::parse_input, SAMPLE).unwrap();
+        let (energy, moves) = organize(&burrow).unwrap();
+
+        let organized = moves.iter().fold(burrow, |burrow, step| burrow.apply(step));
+
+        assert_eq!(energy, 12521);
+        assert_eq!(moves.iter().map(|step| step.energy).sum::<usize>(), energy);
+        assert!(organized.is_organized());
+    }
+
+    #[test]
+    fn sample_moves() {
+        use data::Location::{Hallway, Room};
+
+        // Moves shown in the puzzle, with each trip between rooms split at the hallway
+        let moves = [
+            (Room(2, 0), Hallway(3)),
+            (Room(1, 0), Hallway(5)),
+            (Hallway(5), Room(2, 0)),
+            (Room(1, 1), Hallway(5)),
+            (Hallway(3), Room(1, 1)),
+            (Room(0, 0), Hallway(3)),
+            (Hallway(3), Room(1, 0)),
+            (Room(3, 0), Hallway(7)),
+            (Room(3, 1), Hallway(9)),
+            (Hallway(7), Room(3, 1)),
+            (Hallway(5), Room(3, 0)),
+            (Hallway(9), Room(0, 0)),
+        ];
+
+        let mut burrow: Burrow<2> = parse_all(data::parse_input, SAMPLE).unwrap();
+        let mut energy = 0;
+
+        for (from, to) in moves {
+            let step = burrow
+                .moves()
+                .into_iter()
+                .find(|step| step.from == from && step.to == to)
+                .unwrap_or_else(|| panic!("cannot move from {} to {} in\n{}", from, to, burrow));
+
+            burrow = burrow.apply(&step);
+            energy += step.energy;
+        }
+
+        assert!(burrow.is_organized());
+        assert_eq!(energy, 12521);
+    }
+
+    #[test]
+    fn unfold() {
+        let burrow: Burrow<2> = parse_all(data::parse_input, SAMPLE).unwrap();
+        let unfolded: Burrow<4> = burrow.unfold(&FOLDED_ROWS);
+
+        assert_eq!(
+            unfolded.to_string(),
+            "#############\n\
+             #...........#\n\
+             ###B#C#B#D###\n  \
+             #D#C#B#A#\n  \
+             #D#B#A#C#\n  \
+             #A#D#C#A#\n  \
+             #########"
+        );
+    }
+}
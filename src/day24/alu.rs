@@ -0,0 +1,144 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Interpreter for programs of the arithmetic logic unit
+
+use super::data::{Instruction, Operand, Operation, Register};
+
+/// State of the four registers of the unit
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Alu {
+    registers: [i64; 4],
+}
+
+impl Alu {
+    pub fn get(&self, register: Register) -> i64 {
+        self.registers[register.index()]
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.get(register),
+            Operand::Number(number) => number,
+        }
+    }
+
+    /// Runs a program from the current state, reading each `inp` from the given inputs
+    ///
+    /// # Errors
+    /// An error is returned if the program reads more inputs than given or performs an invalid
+    /// division or modulo.
+    pub fn run(
+        &mut self,
+        program: &[Instruction],
+        inputs: impl IntoIterator<Item = i64>,
+    ) -> anyhow::Result<()> {
+        let mut inputs = inputs.into_iter();
+
+        for (index, instruction) in program.iter().enumerate() {
+            self.execute(instruction, &mut inputs)
+                .map_err(|err| err.context(format!("executing `{}` at {}", instruction, index)))?;
+        }
+
+        Ok(())
+    }
+
+    fn execute(
+        &mut self,
+        instruction: &Instruction,
+        inputs: &mut impl Iterator<Item = i64>,
+    ) -> anyhow::Result<()> {
+        let (register, result) = match *instruction {
+            Instruction::Inp(register) => (
+                register,
+                inputs
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("Ran out of inputs"))?,
+            ),
+            Instruction::Op(operation, register, operand) => {
+                let (a, b) = (self.get(register), self.value(operand));
+
+                let result = match operation {
+                    Operation::Add => a.checked_add(b),
+                    Operation::Mul => a.checked_mul(b),
+                    Operation::Div => a.checked_div(b),
+                    Operation::Mod if a < 0 || b <= 0 => None,
+                    Operation::Mod => Some(a % b),
+                    Operation::Eql => Some(i64::from(a == b)),
+                };
+
+                (
+                    register,
+                    result
+                        .ok_or_else(|| anyhow::anyhow!("Invalid operation on {} and {}", a, b))?,
+                )
+            }
+        };
+
+        self.registers[register.index()] = result;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day24::data::parse_input;
+    use crate::nom::parse_all;
+    use test_case::test_case;
+
+    const NEGATE: &str = "inp x\nmul x -1";
+    const TRIPLE: &str = "inp z\ninp x\nmul z 3\neql z x";
+    const BINARY: &str = "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\n\
+                          div w 2\nadd x w\nmod x 2\ndiv w 2\nmod w 2";
+
+    fn run(program: &str, inputs: &[i64]) -> anyhow::Result<Alu> {
+        let program = parse_all(parse_input, program)?;
+        let mut alu = Alu::default();
+
+        alu.run(&program, inputs.iter().copied())?;
+
+        Ok(alu)
+    }
+
+    #[test_case(NEGATE, &[7], Register::X => -7 ; "negate")]
+    #[test_case(TRIPLE, &[3, 9], Register::Z => 1 ; "triple")]
+    #[test_case(TRIPLE, &[3, 8], Register::Z => 0 ; "not triple")]
+    #[test_case(BINARY, &[13], Register::W => 1 ; "eights bit")]
+    #[test_case(BINARY, &[13], Register::X => 1 ; "fours bit")]
+    #[test_case(BINARY, &[13], Register::Y => 0 ; "twos bit")]
+    #[test_case(BINARY, &[13], Register::Z => 1 ; "ones bit")]
+    fn registers(program: &str, inputs: &[i64], register: Register) -> i64 {
+        run(program, inputs).unwrap().get(register)
+    }
+
+    #[test_case("inp x\ndiv x 0", &[1] ; "division by zero")]
+    #[test_case("inp x\nmod x 0", &[1] ; "modulo by zero")]
+    #[test_case("inp x\nmod x 2", &[-1] ; "negative modulo")]
+    #[test_case("inp x\ninp y", &[1] ; "missing input")]
+    fn invalid(program: &str, inputs: &[i64]) {
+        assert!(run(program, inputs).is_err());
+    }
+}
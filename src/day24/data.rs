@@ -0,0 +1,180 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use crate::nom::{lines, ParseResult};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, i64 as parse_i64, newline};
+use nom::combinator::{map, opt, value};
+use nom::error::context;
+use nom::sequence::{pair, preceded, separated_pair, terminated};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+/// Second argument of an instruction, either a register or a literal number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Register(Register),
+    Number(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Add,
+    Mul,
+    Div,
+    Mod,
+    Eql,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    /// Reads the next input into the register
+    Inp(Register),
+    /// Stores the result of the operation between the register and the operand in the register
+    Op(Operation, Register, Operand),
+}
+
+impl Register {
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Register::W => "w",
+            Register::X => "x",
+            Register::Y => "y",
+            Register::Z => "z",
+        })
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::Register(register) => register.fmt(f),
+            Operand::Number(number) => number.fmt(f),
+        }
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Operation::Add => "add",
+            Operation::Mul => "mul",
+            Operation::Div => "div",
+            Operation::Mod => "mod",
+            Operation::Eql => "eql",
+        })
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Inp(register) => write!(f, "inp {}", register),
+            Instruction::Op(operation, register, operand) => {
+                write!(f, "{} {} {}", operation, register, operand)
+            }
+        }
+    }
+}
+
+fn parse_register(input: &str) -> ParseResult<'_, Register> {
+    alt((
+        value(Register::W, char('w')),
+        value(Register::X, char('x')),
+        value(Register::Y, char('y')),
+        value(Register::Z, char('z')),
+    ))(input)
+}
+
+fn parse_operand(input: &str) -> ParseResult<'_, Operand> {
+    alt((
+        map(parse_register, Operand::Register),
+        map(parse_i64, Operand::Number),
+    ))(input)
+}
+
+fn parse_operation(input: &str) -> ParseResult<'_, Operation> {
+    alt((
+        value(Operation::Add, tag("add")),
+        value(Operation::Mul, tag("mul")),
+        value(Operation::Div, tag("div")),
+        value(Operation::Mod, tag("mod")),
+        value(Operation::Eql, tag("eql")),
+    ))(input)
+}
+
+fn parse_instruction(input: &str) -> ParseResult<'_, Instruction> {
+    context(
+        "instruction",
+        alt((
+            map(preceded(tag("inp "), parse_register), Instruction::Inp),
+            map(
+                pair(
+                    terminated(parse_operation, char(' ')),
+                    separated_pair(parse_register, char(' '), parse_operand),
+                ),
+                |(operation, (register, operand))| Instruction::Op(operation, register, operand),
+            ),
+        )),
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> ParseResult<'_, Vec<Instruction>> {
+    terminated(lines(parse_instruction), opt(newline))(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::nom::parse_all;
+
+    #[test]
+    fn display_round_trip() {
+        let program = "inp w\nadd z w\nmod z 2\ndiv w -2\neql x y";
+        let instructions = parse_all(parse_input, program).unwrap();
+
+        let rendered: Vec<_> = instructions.iter().map(ToString::to_string).collect();
+
+        assert_eq!(rendered.join("\n"), program);
+    }
+
+    #[test]
+    fn unknown_instruction() {
+        assert!(parse_all(parse_input, "inp w\nsub w 1").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 24 of Advent of Code 2021
+
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+use alu::Alu;
+use data::{Instruction, Register};
+use monad::Preference;
+
+mod alu;
+mod data;
+mod monad;
+
+pub const DAY: Day = Day {
+    number: 24,
+    inputs: lazy_input!(24),
+    embedded: embedded_input!(24, "answers.toml", "sample.txt"),
+    default_inputs: &["sample"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(data::parse_input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        find_model_number(input, Preference::Largest).map(Answer::from)
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        find_model_number(input, Preference::Smallest).map(Answer::from)
+    }
+}
+
+/// Runs the program on the digits of a model number, returning whether it was accepted
+fn accepts(program: &[Instruction], digits: &[i64]) -> anyhow::Result<bool> {
+    let mut alu = Alu::default();
+
+    alu.run(program, digits.iter().copied())?;
+
+    Ok(alu.get(Register::Z) == 0)
+}
+
+fn find_model_number(program: &[Instruction], preference: Preference) -> anyhow::Result<u64> {
+    let blocks = monad::extract_blocks(program)?;
+    let digits = monad::model_number(&blocks, preference)?;
+
+    // Double check the analysis against the program itself
+    anyhow::ensure!(
+        accepts(program, &digits)?,
+        "model number {:?} was rejected by the program",
+        digits
+    );
+
+    Ok(digits
+        .iter()
+        .fold(0, |number, &digit| number * 10 + digit as u64))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("input/sample.txt");
+
+    fn digits(number: u64) -> Vec<i64> {
+        number
+            .to_string()
+            .bytes()
+            .map(|digit| i64::from(digit - b'0'))
+            .collect()
+    }
+
+    #[test_case(Preference::Largest => 21968959919499 ; "largest")]
+    #[test_case(Preference::Smallest => 11411512916198 ; "smallest")]
+    fn model_number(preference: Preference) -> u64 {
+        let program = parse_all(data::parse_input, SAMPLE).unwrap();
+        let number = find_model_number(&program, preference).unwrap();
+
+        assert!(accepts(&program, &digits(number)).unwrap());
+
+        number
+    }
+
+    #[test_case(21968959919498 ; "last digit lowered")]
+    #[test_case(31968959919499 ; "above largest")]
+    #[test_case(11411512916188 ; "paired digit changed")]
+    fn rejected(number: u64) {
+        let program = parse_all(data::parse_input, SAMPLE).unwrap();
+
+        assert!(!accepts(&program, &digits(number)).unwrap());
+    }
+
+    #[test]
+    fn unexpected_shape() {
+        let program = parse_all(data::parse_input, "inp w\nadd z w").unwrap();
+
+        assert!(find_model_number(&program, Preference::Largest).is_err());
+    }
+}
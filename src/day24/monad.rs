@@ -0,0 +1,174 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Analysis of the model number validation program
+//!
+//! MONAD is made of 14 blocks of identical instructions, one per digit, that only differ in three
+//! parameters. Register `z` is used as a stack of base 26 numbers: each block either pushes the
+//! digit plus an offset, or pops the top value and pushes again unless the digit matches the popped
+//! value plus a check. A model number is valid when every pop matches, leaving `z` at zero.
+
+use super::data::{Instruction, Operand, Operation, Register};
+
+/// Number of digits in a model number
+pub const DIGITS: usize = 14;
+
+/// Number of instructions validating each digit
+const BLOCK_LENGTH: usize = 18;
+
+/// Parameters of the block of instructions validating a single digit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block {
+    /// Either 1 when pushing onto `z` or 26 when popping from it
+    pub divisor: i64,
+    /// Added to the popped value before comparing it with the digit
+    pub check: i64,
+    /// Added to the digit before pushing it
+    pub offset: i64,
+}
+
+/// Which of the accepted model numbers to find
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preference {
+    Largest,
+    Smallest,
+}
+
+impl Block {
+    /// Instructions of a block with these parameters
+    fn instructions(&self) -> Vec<Instruction> {
+        use Instruction::{Inp, Op};
+        use Operand::{Number, Register as Reg};
+        use Operation::*;
+        use Register::*;
+
+        vec![
+            Inp(W),
+            Op(Mul, X, Number(0)),
+            Op(Add, X, Reg(Z)),
+            Op(Mod, X, Number(26)),
+            Op(Div, Z, Number(self.divisor)),
+            Op(Add, X, Number(self.check)),
+            Op(Eql, X, Reg(W)),
+            Op(Eql, X, Number(0)),
+            Op(Mul, Y, Number(0)),
+            Op(Add, Y, Number(25)),
+            Op(Mul, Y, Reg(X)),
+            Op(Add, Y, Number(1)),
+            Op(Mul, Z, Reg(Y)),
+            Op(Mul, Y, Number(0)),
+            Op(Add, Y, Reg(W)),
+            Op(Add, Y, Number(self.offset)),
+            Op(Mul, Y, Reg(X)),
+            Op(Add, Z, Reg(Y)),
+        ]
+    }
+
+    fn parse(instructions: &[Instruction]) -> Option<Block> {
+        let number = |index: usize| match instructions.get(index)? {
+            Instruction::Op(_, _, Operand::Number(number)) => Some(*number),
+            _ => None,
+        };
+
+        let block = Block {
+            divisor: number(4)?,
+            check: number(5)?,
+            offset: number(15)?,
+        };
+
+        (block.instructions() == instructions).then_some(block)
+    }
+}
+
+/// Extracts the parameters of every block of the program
+///
+/// # Errors
+/// An error is returned if the program is not made of the expected blocks.
+pub fn extract_blocks(program: &[Instruction]) -> anyhow::Result<Vec<Block>> {
+    anyhow::ensure!(
+        program.len() == DIGITS * BLOCK_LENGTH,
+        "expected {} instructions, found {}",
+        DIGITS * BLOCK_LENGTH,
+        program.len()
+    );
+
+    program
+        .chunks(BLOCK_LENGTH)
+        .enumerate()
+        .map(|(digit, instructions)| {
+            Block::parse(instructions)
+                .ok_or_else(|| anyhow::anyhow!("block for digit {} has an unexpected shape", digit))
+        })
+        .collect()
+}
+
+/// Finds the model number accepted by the program described by the blocks
+///
+/// # Errors
+/// An error is returned if the blocks do not pair up or if no digits satisfy a pair.
+pub fn model_number(blocks: &[Block], preference: Preference) -> anyhow::Result<[i64; DIGITS]> {
+    anyhow::ensure!(blocks.len() == DIGITS, "expected {} blocks", DIGITS);
+
+    let mut digits = [0; DIGITS];
+    let mut stack = Vec::new();
+
+    for (digit, block) in blocks.iter().enumerate() {
+        match block.divisor {
+            // Any digit matching would make the check pass and skip the push
+            1 if block.check > 9 => stack.push(digit),
+            26 => {
+                let pushed = stack
+                    .pop()
+                    .ok_or_else(|| anyhow::anyhow!("digit {} pops from an empty stack", digit))?;
+
+                // The popped digit plus the difference must equal this one
+                let difference = blocks[pushed].offset + block.check;
+                let first = match preference {
+                    Preference::Largest => 9.min(9 - difference),
+                    Preference::Smallest => 1.max(1 - difference),
+                };
+
+                anyhow::ensure!(
+                    (1..=9).contains(&first) && (1..=9).contains(&(first + difference)),
+                    "digits {} and {} cannot differ by {}",
+                    pushed,
+                    digit,
+                    difference
+                );
+
+                digits[pushed] = first;
+                digits[digit] = first + difference;
+            }
+            _ => anyhow::bail!("digit {} has an unsupported block {:?}", digit, block),
+        }
+    }
+
+    anyhow::ensure!(
+        stack.is_empty(),
+        "digits {:?} are pushed but never popped",
+        stack
+    );
+
+    Ok(digits)
+}
@@ -0,0 +1,104 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+use crate::grid::Grid;
+use crate::viz::{Color, Frame, Glyph};
+use std::fmt::{Display, Formatter, Write};
+
+/// Content of a single location of the sea floor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+/// Map of the sea floor, wrapping around on both axes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeaFloor(pub Grid<Cell>);
+
+impl Cell {
+    fn symbol(self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::East => '>',
+            Cell::South => 'v',
+        }
+    }
+}
+
+impl SeaFloor {
+    /// Picture of both herds on the sea floor
+    pub fn frame(&self, caption: String) -> Frame {
+        Frame {
+            caption,
+            cells: self.0.map(|&cell| {
+                let color = match cell {
+                    Cell::Empty => Color::Blue,
+                    Cell::East => Color::LightGreen,
+                    Cell::South => Color::LightMagenta,
+                };
+
+                Glyph::new(cell.symbol(), color)
+            }),
+        }
+    }
+}
+
+impl TryFrom<char> for Cell {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Cell::Empty),
+            '>' => Ok(Cell::East),
+            'v' => Ok(Cell::South),
+            _ => Err(anyhow::anyhow!("unknown sea floor cell {:?}", value)),
+        }
+    }
+}
+
+impl Display for SeaFloor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.0.rows().enumerate() {
+            if y > 0 {
+                f.write_char('\n')?;
+            }
+
+            for cell in row {
+                f.write_char(cell.symbol())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the map of the sea floor
+///
+/// # Errors
+/// An error is returned if the map is not rectangular or contains unknown cells.
+pub fn parse_input(input: &str) -> anyhow::Result<SeaFloor> {
+    Grid::parse_with(input, Cell::try_from).map(SeaFloor)
+}
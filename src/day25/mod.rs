@@ -0,0 +1,179 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 25 of Advent of Code 2021
+
+use crate::registry::Day;
+use crate::viz::Animation;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+use data::{Cell, SeaFloor};
+
+mod data;
+
+pub const DAY: Day = Day {
+    number: 25,
+    inputs: lazy_input!(25),
+    embedded: embedded_input!(25, "answers.toml", "sample.txt"),
+    default_inputs: &["sample"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = SeaFloor;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        data::parse_input(content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        let last = input
+            .steps()
+            .last()
+            .unwrap_or_else(|| unreachable!("the herds always take at least one step"));
+
+        Ok(last.number.into())
+    }
+
+    /// The last day has no second challenge, only a sleigh to start
+    fn challenge_two(&self, _input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(Answer::Text("Merry Christmas!".to_string()))
+    }
+
+    /// Moves the herds until they stop
+    fn animate(&self, input: &Self::Input) -> Option<Box<dyn Animation>> {
+        let initial = input.frame("initial state".to_string());
+        let steps = input.steps().map(|step| {
+            step.floor
+                .frame(format!("step {}: {} moved", step.number, step.moved))
+        });
+
+        Some(Box::new(std::iter::once(initial).chain(steps)))
+    }
+}
+
+/// State of the sea floor after one step of the simulation
+#[derive(Debug, Clone)]
+pub struct Step {
+    /// Number of the step, starting from 1
+    pub number: usize,
+    pub floor: SeaFloor,
+    /// Number of sea cucumbers that moved during the step
+    pub moved: usize,
+}
+
+/// Iterator over the steps of the simulation, ending with the first step where nothing moves
+pub struct Steps {
+    floor: SeaFloor,
+    number: usize,
+    done: bool,
+}
+
+impl SeaFloor {
+    /// Simulates the herds until they stop moving
+    pub fn steps(&self) -> Steps {
+        Steps {
+            floor: self.clone(),
+            number: 0,
+            done: false,
+        }
+    }
+
+    /// Moves every sea cucumber of the herd facing the direction whose destination was empty
+    fn move_herd(&mut self, herd: Cell, (dx, dy): (usize, usize)) -> usize {
+        let grid = &self.0;
+        let (width, height) = (grid.width(), grid.height());
+
+        let moving: Vec<_> = grid
+            .iter()
+            .filter(|&(_, &cell)| cell == herd)
+            .map(|((x, y), _)| ((x, y), ((x + dx) % width, (y + dy) % height)))
+            .filter(|&(_, destination)| grid[destination] == Cell::Empty)
+            .collect();
+
+        for &(origin, destination) in &moving {
+            self.0[origin] = Cell::Empty;
+            self.0[destination] = herd;
+        }
+
+        moving.len()
+    }
+
+    /// Moves the east-facing herd, then the south-facing one, returning how many moved
+    pub fn step(&mut self) -> usize {
+        self.move_herd(Cell::East, (1, 0)) + self.move_herd(Cell::South, (0, 1))
+    }
+}
+
+impl Iterator for Steps {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let moved = self.floor.step();
+
+        self.number += 1;
+        self.done = moved == 0;
+
+        Some(Step {
+            number: self.number,
+            floor: self.floor.clone(),
+            moved,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("input/sample.txt");
+
+    #[test_case("...>>>>>...", 1 => "...>>>>.>.." ; "east herd")]
+    #[test_case("...>>>>>...", 2 => "...>>>.>.>." ; "east herd twice")]
+    #[test_case("..........\n.>v....v..\n.......>..\n..........", 1 => "..........\n.>........\n..v....v>.\n.........." ; "east before south")]
+    #[test_case("...>...\n.......\n......>\nv.....>\n......>\n.......\n..vvv..", 4 => ">......\n..v....\n..>.v..\n.>.v...\n...>...\n.......\nv......" ; "wrapping around")]
+    fn after_steps(input: &str, steps: usize) -> String {
+        let floor = data::parse_input(input).unwrap();
+
+        floor.steps().nth(steps - 1).unwrap().floor.to_string()
+    }
+
+    #[test]
+    fn stops_moving() {
+        let floor = data::parse_input(SAMPLE).unwrap();
+        let steps: Vec<_> = floor.steps().collect();
+
+        assert_eq!(steps.len(), 58);
+        assert_eq!(steps[57].moved, 0);
+        assert!(steps[..57].iter().all(|step| step.moved > 0));
+        assert_eq!(steps[56].floor, steps[57].floor);
+    }
+}
@@ -42,10 +42,10 @@ impl StateCounter {
 
 impl IntoIterator for StateCounter {
     type Item = (usize, usize);
-    type IntoIter = impl Iterator<Item = Self::Item>;
+    type IntoIter = std::iter::Zip<std::vec::IntoIter<usize>, std::vec::IntoIter<usize>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter().zip(self.1.into_iter())
+        self.0.into_iter().zip(self.1)
     }
 }
 
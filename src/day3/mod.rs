@@ -22,17 +22,15 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 3 of Advent of Code 2021
-#![feature(type_alias_impl_trait)]
+//! Solution for day 3 of Advent of Code 2021
 
-use anyhow::{bail, Context, Error};
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+use anyhow::{bail, Error};
 use itertools::Itertools;
 use std::str::FromStr;
 
-static INPUT_DIR: LazyInputProvider = lazy_input!(3);
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum State {
     Zero,
@@ -70,31 +68,28 @@ impl FromStr for Matrix {
 mod challenge_one;
 mod challenge_two;
 
-fn process(name: &str) -> anyhow::Result<()> {
-    let content = INPUT_DIR
-        .get_input(&format!("{}.txt", name))
-        .context("reading content")?;
-
-    let input: Matrix = content.parse()?;
+pub const DAY: Day = Day {
+    number: 3,
+    inputs: lazy_input!(3),
+    embedded: embedded_input!(3, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
 
-    println!(
-        "Challenge one ({}): {}",
-        name,
-        challenge_one::challenge_one(&input)
-    );
+pub struct Solver;
 
-    println!(
-        "Challenge two ({}): {}",
-        name,
-        challenge_two::challenge_two(&input)
-    );
+impl Solution for Solver {
+    type Input = Matrix;
 
-    Ok(())
-}
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        content.parse()
+    }
 
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one::challenge_one(input).into())
+    }
 
-    Ok(())
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two::challenge_two(input).into())
+    }
 }
@@ -22,23 +22,18 @@
  * SOFTWARE.
  */
 
-use aoc2021::nom::parse_usize_matrix;
+use crate::nom::parse_usize_matrix;
 use nom::error::VerboseError;
 use nom::Parser;
 use std::fmt::{Debug, Formatter};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
 pub enum CellState {
+    #[default]
     Clear,
     Marked,
 }
 
-impl Default for CellState {
-    fn default() -> Self {
-        Self::Clear
-    }
-}
-
 #[derive(Default, Clone, Copy)]
 pub struct Board {
     values: [[usize; 5]; 5],
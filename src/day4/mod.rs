@@ -22,28 +22,50 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 4 of Advent of Code 2021
-#![feature(type_alias_impl_trait)]
+//! Solution for day 4 of Advent of Code 2021
 #![allow(dead_code)]
 #![allow(unused)]
 
+use crate::nom::{parse_all, parse_usize};
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
 use anyhow::{bail, Context, Error};
-use aoc2021::{lazy_input, LazyInputProvider};
-use aoc2021::{
-    nom::{parse_all, parse_usize},
-    InputProvider,
-};
 use itertools::Itertools;
 use nom::error::VerboseError;
 use nom::{Finish, InputIter, Parser, ToUsize};
 use tap::{Tap, TapFallible};
 
-static INPUT_DIR: LazyInputProvider = lazy_input!(4);
-
 mod board;
 
+pub const DAY: Day = Day {
+    number: 4,
+    inputs: lazy_input!(4),
+    embedded: embedded_input!(4, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = InputData;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(input_parser(), content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_one(input).map(Answer::from)
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_two(input).map(Answer::from)
+    }
+}
+
 #[derive(Debug, Default)]
-struct InputData {
+pub struct InputData {
     chosen_numbers: Vec<usize>,
     boards: Vec<board::Board>,
 }
@@ -94,32 +116,3 @@ fn challenge_two(input: &InputData) -> anyhow::Result<usize> {
 
     bail!("No winning score found")
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let content = INPUT_DIR
-        .get_input(&format!("{}.txt", name))
-        .context("reading content")?;
-
-    let data = parse_all(input_parser(), content.as_str())?;
-
-    println!(
-        "Challenge one ({}): {}",
-        name,
-        challenge_one(&data).context("challenge one")?
-    );
-
-    println!(
-        "Challenge two ({}): {}",
-        name,
-        challenge_two(&data).context("challenge two")?
-    );
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
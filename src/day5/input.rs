@@ -22,12 +22,14 @@
  * SOFTWARE.
  */
 
-use aoc2021::nom::{parse_usize, ParseResult};
+use crate::image::{Svg, WHITE};
+use crate::nom::{lines, parse_usize, ParseResult};
 use itertools::{EitherOrBoth, Itertools};
 use nom::error::VerboseError;
 use nom::{IResult, InputIter, InputLength};
 use std::fmt::{Debug, Display, Formatter, Write};
 
+#[derive(Debug)]
 pub struct Data {
     pub lines: Vec<Line>,
 }
@@ -35,7 +37,7 @@ pub struct Data {
 pub struct Parser;
 
 impl Parser {
-    fn point(input: &str) -> ParseResult<Point> {
+    fn point(input: &str) -> ParseResult<'_, Point> {
         nom::combinator::map(
             nom::sequence::separated_pair(
                 parse_usize,
@@ -46,7 +48,7 @@ impl Parser {
         )(input)
     }
 
-    fn line(input: &str) -> ParseResult<Line> {
+    fn line(input: &str) -> ParseResult<'_, Line> {
         nom::combinator::map(
             nom::sequence::separated_pair(
                 Self::point,
@@ -57,11 +59,8 @@ impl Parser {
         )(input)
     }
 
-    pub fn input(input: &str) -> ParseResult<Data> {
-        nom::combinator::map(
-            nom::multi::separated_list1(nom::character::complete::newline, Self::line),
-            |lines| Data { lines },
-        )(input)
+    pub fn input(input: &str) -> ParseResult<'_, Data> {
+        nom::combinator::map(lines(Self::line), |lines| Data { lines })(input)
     }
 }
 
@@ -234,6 +233,39 @@ impl Diagram {
     }
 }
 
+impl Data {
+    /// Drawing of the vents, diagonal ones in a different color and overlaps marked with dots
+    pub fn svg(&self) -> Svg {
+        let max = self.lines.iter().fold((0, 0), |(x, y), Line(a, b)| {
+            (x.max(a.0).max(b.0), y.max(a.1).max(b.1))
+        });
+
+        // Leave half a unit around the drawing so the ends of the lines are not cut off
+        let mut svg = Svg::new((-0.5, -0.5), (max.0 as f64 + 0.5, max.1 as f64 + 0.5));
+        let width = svg.stroke(0.002).max(0.2);
+
+        let mut diagram = Diagram::new();
+
+        for line in &self.lines {
+            let color = if line.is_diagonal() {
+                [17, 168, 205]
+            } else {
+                WHITE
+            };
+            let point = |p: Point| (p.0 as f64, p.1 as f64);
+
+            svg.line(point(line.0), point(line.1), color, width, 0.6);
+            diagram.add_line(line);
+        }
+
+        for point in diagram.get_intersections() {
+            svg.circle((point.0 as f64, point.1 as f64), width, [241, 76, 76]);
+        }
+
+        svg
+    }
+}
+
 impl Display for Diagram {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let max_x = self.points.iter().map(|p| p.0).max().unwrap();
@@ -303,3 +335,15 @@ fn drawing_sample_lines() {
          222111....\n"
     );
 }
+
+#[test]
+fn bad_line_location() {
+    let error = crate::nom::parse_all(Parser::input, "0,9 -> 5,9\n8,0 -> 0,8\n9,4 => 3,4\n")
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(
+        error,
+        "line 3, column 4: Tag failed at ' '\n  3 | 9,4 => 3,4\n    |    ^\n  in line (line 3, column 1)"
+    );
+}
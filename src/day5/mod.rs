@@ -22,22 +22,50 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 5 of Advent of Code 2021
-#![feature(int_abs_diff)]
+//! Solution for day 5 of Advent of Code 2021
 #![allow(dead_code)]
 #![allow(unused)]
 
-use anyhow::Context;
-use aoc2021::nom::parse_all;
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
+use crate::image::Image;
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
 use itertools::Itertools;
 use nom::Parser;
 
-static INPUT_DIR: LazyInputProvider = lazy_input!(5);
-
 mod input;
 
+pub const DAY: Day = Day {
+    number: 5,
+    inputs: lazy_input!(5),
+    embedded: embedded_input!(5, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = input::Data;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(input::Parser::input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two(input).into())
+    }
+
+    fn draw(&self, input: &Self::Input) -> Option<Image> {
+        Some(Image::Vector(input.svg()))
+    }
+}
+
 fn challenge_one(input: &input::Data) -> usize {
     let mut diagram = input::Diagram::new();
 
@@ -57,26 +85,3 @@ fn challenge_two(input: &input::Data) -> usize {
 
     diagram.get_intersections().len()
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let data = parse_all(
-        input::Parser::input,
-        INPUT_DIR
-            .get_input(&format!("{}.txt", name))
-            .context("reading content")?
-            .as_str(),
-    )?;
-
-    println!("Challenge one ({}): {}", name, challenge_one(&data));
-
-    println!("Challenge two ({}): {}", name, challenge_two(&data));
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
@@ -22,15 +22,39 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 6 of Advent of Code 2021
+//! Solution for day 6 of Advent of Code 2021
 
-use anyhow::Context;
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
 use itertools::Itertools;
 use std::str::FromStr;
 
-static INPUT_DIR: LazyInputProvider = lazy_input!(6);
+pub const DAY: Day = Day {
+    number: 6,
+    inputs: lazy_input!(6),
+    embedded: embedded_input!(6, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<u8>;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_input(content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two(input).into())
+    }
+}
 
 #[derive(Debug)]
 struct FastFishes([usize; 9]);
@@ -53,6 +77,10 @@ impl FastFishes {
     }
 }
 
+fn parse_input(content: &str) -> anyhow::Result<Vec<u8>> {
+    Ok(content.split(',').map(FromStr::from_str).try_collect()?)
+}
+
 fn challenge_one(input: &[u8]) -> usize {
     let mut fishes = FastFishes::from_input(input);
 
@@ -72,25 +100,3 @@ fn challenge_two(input: &[u8]) -> usize {
 
     fishes.0.iter().sum()
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let content: Vec<u8> = INPUT_DIR
-        .get_input(&format!("{}.txt", name))
-        .context("reading content")?
-        .split(',')
-        .map(FromStr::from_str)
-        .try_collect()?;
-
-    println!("Challenge one ({}): {}", name, challenge_one(&content[..]));
-
-    println!("Challenge two ({}): {}", name, challenge_two(&content[..]));
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
@@ -22,15 +22,38 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 7 of Advent of Code 2021
+//! Solution for day 7 of Advent of Code 2021
 
-use anyhow::Context;
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
 use itertools::Itertools;
 
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
+pub const DAY: Day = Day {
+    number: 7,
+    inputs: lazy_input!(7),
+    embedded: embedded_input!(7, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
 
-static INPUT_DIR: LazyInputProvider = lazy_input!(7);
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<usize>;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_input(content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two(input).into())
+    }
+}
 
 struct PositionCounters(Vec<usize>);
 
@@ -55,6 +78,10 @@ impl PositionCounters {
     }
 }
 
+fn parse_input(content: &str) -> anyhow::Result<Vec<usize>> {
+    Ok(content.split(',').map(str::parse).try_collect()?)
+}
+
 fn challenge_one(input: &[usize]) -> usize {
     let positions = PositionCounters::from_input(input);
 
@@ -80,7 +107,7 @@ fn challenge_two(input: &[usize]) -> usize {
 
     for (i, count) in positions.iter().enumerate() {
         for (j, slot) in costs.iter_mut().enumerate() {
-            let distance = if i < j { j - i } else { i - j };
+            let distance = j.abs_diff(i);
 
             *slot += count * (distance + 1) * distance / 2;
         }
@@ -88,25 +115,3 @@ fn challenge_two(input: &[usize]) -> usize {
 
     *costs.iter().min().unwrap()
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let content: Vec<usize> = INPUT_DIR
-        .get_input(&format!("{}.txt", name))
-        .context("reading content")?
-        .split(',')
-        .map(str::parse)
-        .try_collect()?;
-
-    println!("Challenge one ({}): {}", name, challenge_one(&content));
-
-    println!("Challenge two ({}): {}", name, challenge_two(&content));
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
@@ -22,7 +22,7 @@
  * SOFTWARE.
  */
 
-use aoc2021::nom::ParseResult;
+use crate::nom::{lines, ParseResult};
 
 #[repr(u8)]
 enum Segment {
@@ -57,7 +57,7 @@ pub struct DisplayPatterns([DisplayState; 10]);
 
 impl IntoIterator for DisplayPatterns {
     type Item = DisplayState;
-    type IntoIter = impl Iterator<Item = Self::Item>;
+    type IntoIter = std::array::IntoIter<DisplayState, 10>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.into_iter()
@@ -84,7 +84,7 @@ impl Data {
 pub struct Parser;
 
 impl Parser {
-    fn parse_display(s: &str) -> ParseResult<DisplayState> {
+    fn parse_display(s: &str) -> ParseResult<'_, DisplayState> {
         nom::sequence::preceded(
             nom::character::complete::space0,
             nom::multi::fold_many1(
@@ -107,7 +107,7 @@ impl Parser {
         )(s)
     }
 
-    fn parse_digits<const N: usize>(s: &str) -> ParseResult<[DisplayState; N]> {
+    fn parse_digits<const N: usize>(s: &str) -> ParseResult<'_, [DisplayState; N]> {
         let mut digits = [DisplayState::default(); N];
 
         let result = nom::multi::fill(Self::parse_display, &mut digits)(s);
@@ -115,11 +115,11 @@ impl Parser {
         result.map(|(r, _)| (r, digits))
     }
 
-    fn parse_patterns(s: &str) -> ParseResult<DisplayPatterns> {
+    fn parse_patterns(s: &str) -> ParseResult<'_, DisplayPatterns> {
         nom::combinator::map(Self::parse_digits::<10>, DisplayPatterns)(s)
     }
 
-    fn parse_entry(s: &str) -> ParseResult<Entry> {
+    fn parse_entry(s: &str) -> ParseResult<'_, Entry> {
         nom::combinator::map(
             nom::sequence::separated_pair(
                 Self::parse_patterns,
@@ -130,11 +130,8 @@ impl Parser {
         )(s)
     }
 
-    pub fn parse_input(s: &str) -> ParseResult<Data> {
-        nom::combinator::map(
-            nom::multi::separated_list0(nom::character::complete::newline, Self::parse_entry),
-            |entries| Data { entries },
-        )(s)
+    pub fn parse_input(s: &str) -> ParseResult<'_, Data> {
+        nom::combinator::map(lines(Self::parse_entry), |entries| Data { entries })(s)
     }
 }
 
@@ -22,20 +22,43 @@
  * SOFTWARE.
  */
 
-//! Binary for solving day 8 of Advent of Code 2021
-#![feature(type_alias_impl_trait)]
+//! Solution for day 8 of Advent of Code 2021
 
-use anyhow::Context;
-use aoc2021::nom::parse_all;
-use aoc2021::InputProvider;
-use aoc2021::{lazy_input, LazyInputProvider};
+use crate::nom::parse_all;
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
 use arrayvec::ArrayVec;
 use itertools::Itertools;
 
 mod input;
 mod process;
 
-static INPUT_DIR: LazyInputProvider = lazy_input!(8);
+pub const DAY: Day = Day {
+    number: 8,
+    inputs: lazy_input!(8),
+    embedded: embedded_input!(8, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = input::Data;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        parse_all(input::Parser::parse_input, content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        challenge_one(input).map(Answer::from)
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two(input).into())
+    }
+}
 
 fn challenge_one(input: &input::Data) -> anyhow::Result<usize> {
     input
@@ -79,30 +102,3 @@ fn challenge_two(input: &input::Data) -> usize {
         })
         .sum()
 }
-
-fn process(name: &str) -> anyhow::Result<()> {
-    let data = parse_all(
-        input::Parser::parse_input,
-        INPUT_DIR
-            .get_input(&format!("{}.txt", name))
-            .context("reading content")?
-            .as_str(),
-    )?;
-
-    println!(
-        "Challenge one ({}): {}",
-        name,
-        challenge_one(&data).context("challenge one")?
-    );
-
-    println!("Challenge two ({}): {}", name, challenge_two(&data));
-
-    Ok(())
-}
-
-fn main() -> anyhow::Result<()> {
-    process("sample").context("sample data")?;
-    process("input").context("real data")?;
-
-    Ok(())
-}
@@ -22,7 +22,7 @@
  * SOFTWARE.
  */
 
-use crate::input::{DisplayPatterns, DisplayState};
+use super::input::{DisplayPatterns, DisplayState};
 use itertools::Itertools;
 
 /// Represent the deduced patterns of the display in order.
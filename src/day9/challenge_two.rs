@@ -22,99 +22,54 @@
  * SOFTWARE.
  */
 
+use crate::grid::{Grid, Position};
 use itertools::Itertools;
 use std::fmt::{Debug, Formatter};
 
 struct Controller {
-    input: Vec<Vec<u32>>,
-    counter: Vec<Vec<u32>>,
+    input: Grid<u32>,
+    counter: Grid<u32>,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Coordinate(usize, usize);
-
 impl Controller {
-    fn neighboors_of(&self, point: Coordinate) -> Vec<Coordinate> {
-        let mut neighboors = Vec::with_capacity(4);
-        let x = point.0;
-        let y = point.1;
-        if x > 0 {
-            neighboors.push(Coordinate(x - 1, y));
-        }
-        if x < self.input[0].len() - 1 {
-            neighboors.push(Coordinate(x + 1, y));
-        }
-        if y > 0 {
-            neighboors.push(Coordinate(x, y - 1));
-        }
-        if y < self.input.len() - 1 {
-            neighboors.push(Coordinate(x, y + 1));
-        }
-        neighboors
-    }
-
-    fn get_value(&self, point: Coordinate) -> u32 {
-        let x = point.0;
-        let y = point.1;
-        self.input[y][x]
-    }
-
-    fn get_counter(&self, point: Coordinate) -> u32 {
-        let x = point.0;
-        let y = point.1;
-        self.counter[y][x]
-    }
-
-    fn get_coordinates(&self) -> Vec<Coordinate> {
-        (0..self.input.len())
-            .cartesian_product(0..self.input[0].len())
-            .map(|(y, x)| Coordinate(x, y))
-            .collect()
-    }
-
-    fn increase_counter(&mut self, point: Coordinate, value: u32) {
-        let x = point.0;
-        let y = point.1;
-        self.counter[y][x] += value;
-    }
-
     fn calculate_descent(&mut self) {
-        let coordinated_values = self.get_coordinates();
-
         for n in (0..9).rev() {
-            for c in coordinated_values.iter().copied() {
-                if self.get_value(c) != n {
+            for position in self.input.positions() {
+                if self.input[position] != n {
                     continue;
                 }
 
-                let counter = self.get_counter(c);
+                let counter = self.counter[position];
+
+                let lower = self
+                    .input
+                    .neighbours4(position)
+                    .find(|&neighbour| self.input[neighbour] < n);
 
-                for neighboor in self.neighboors_of(c) {
-                    if self.get_value(neighboor) < n {
-                        self.increase_counter(neighboor, counter);
-                        break;
-                    }
+                if let Some(neighbour) = lower {
+                    self.counter[neighbour] += counter;
                 }
             }
         }
     }
 
-    fn find_low_points(&self) -> Vec<Coordinate> {
-        self.get_coordinates()
-            .into_iter()
-            .filter(|&point| {
-                self.neighboors_of(point)
-                    .into_iter()
-                    .all(|neighboor| self.get_value(neighboor) > self.get_value(point))
+    fn find_low_points(&self) -> Vec<Position> {
+        self.input
+            .iter()
+            .filter(|&(position, &height)| {
+                self.input
+                    .neighbours4(position)
+                    .all(|neighbour| self.input[neighbour] > height)
             })
+            .map(|(position, _)| position)
             .collect()
     }
 }
 
-pub fn run(input: &[Vec<u32>]) -> u32 {
+pub fn run(input: &Grid<u32>) -> u32 {
     let mut controller = Controller {
-        input: input.to_owned(),
-        counter: vec![vec![1; input[0].len()]; input.len()],
+        input: input.clone(),
+        counter: Grid::filled(input.width(), input.height(), 1),
     };
 
     controller.calculate_descent();
@@ -122,7 +77,7 @@ pub fn run(input: &[Vec<u32>]) -> u32 {
     let mut basin_sizes = controller
         .find_low_points()
         .into_iter()
-        .map(|point| controller.get_counter(point))
+        .map(|point| controller.counter[point])
         .collect_vec();
 
     basin_sizes.sort_unstable();
@@ -132,33 +87,10 @@ pub fn run(input: &[Vec<u32>]) -> u32 {
 
 impl Debug for Controller {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str("Controller {")?;
-        f.write_str("\n  input: ")?;
-
-        // Write input as a matrix
-        for row in &self.input {
-            f.write_str("\n    ")?;
-            for value in row.iter() {
-                f.write_str(&format!("{:>4}", value))?;
-                f.write_str(" ")?;
-            }
-            f.write_str("\n    ")?;
-        }
-
-        f.write_str("\n  counter: ")?;
-
-        // Write counter as a matrix
-        for row in &self.counter {
-            f.write_str("\n    ")?;
-            for value in row.iter() {
-                f.write_str(&format!("{:>4}", value))?;
-                f.write_str(" ")?;
-            }
-            f.write_str("\n    ")?;
-        }
-
-        f.write_str("\n}")?;
-
-        Ok(())
+        write!(
+            f,
+            "Controller {{\n  input:\n{:4?}\n  counter:\n{:4?}\n}}",
+            self.input, self.counter
+        )
     }
 }
@@ -0,0 +1,90 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Solution for day 9 of Advent of Code 2021
+
+use crate::grid::Grid;
+use crate::image::{gradient, Image};
+use crate::registry::Day;
+use crate::{embedded_input, lazy_input};
+use crate::{Answer, Solution};
+
+pub const DAY: Day = Day {
+    number: 9,
+    inputs: lazy_input!(9),
+    embedded: embedded_input!(9, "answers.toml", "input.txt", "sample.txt"),
+    default_inputs: &["sample", "input"],
+    solution: &Solver,
+};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Grid<u32>;
+
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input> {
+        Grid::parse_digits(content)
+    }
+
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_one(input).into())
+    }
+
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(challenge_two::run(input).into())
+    }
+
+    /// Heightmap from dark valleys to bright peaks, with the low points in red
+    fn draw(&self, input: &Self::Input) -> Option<Image> {
+        let mut pixels = input.map(|&height| gradient(f64::from(height) / 9.));
+
+        for (position, _) in input.iter() {
+            if is_low_point(input, position) {
+                pixels[position] = [255, 0, 0];
+            }
+        }
+
+        Some(Image::Raster { pixels, scale: 4 })
+    }
+}
+
+fn is_low_point(input: &Grid<u32>, position: (usize, usize)) -> bool {
+    input
+        .neighbours4(position)
+        .all(|neighbour| input[neighbour] > input[position])
+}
+
+fn challenge_one(input: &Grid<u32>) -> usize {
+    let mut total_sum = 0;
+
+    for (position, &height) in input.iter() {
+        if is_low_point(input, position) {
+            total_sum += height + 1;
+        }
+    }
+
+    total_sum as usize
+}
+
+mod challenge_two;
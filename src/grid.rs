@@ -0,0 +1,520 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Dense rectangular grid shared by the challenges working on 2D maps.
+//!
+//! Positions are `(x, y)` pairs, `x` being the column and `y` the row, with `(0, 0)` at the top left.
+
+use anyhow::{ensure, Context};
+use std::fmt::{Debug, Formatter, Write};
+use std::ops::{Index, IndexMut};
+
+pub type Position = (usize, usize);
+
+/// Offsets of the orthogonal neighbours of a cell
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the orthogonal and diagonal neighbours of a cell
+const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+/// Read-only rectangular window over a [`Grid`]
+#[derive(Copy, Clone)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Position,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to the default value
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        T: Default + Clone,
+    {
+        Self::filled(width, height, T::default())
+    }
+
+    /// Creates a grid with every cell set to the given value
+    #[must_use]
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid from its cells in row-major order
+    ///
+    /// # Errors
+    /// If the number of cells is not a multiple of the width an error is returned
+    pub fn from_cells(width: usize, cells: Vec<T>) -> anyhow::Result<Self> {
+        ensure!(width > 0 || cells.is_empty(), "grid width must not be zero");
+
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        ensure!(
+            height * width == cells.len(),
+            "{} cells do not fill rows of width {}",
+            cells.len(),
+            width
+        );
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Parses a grid with one row per line, converting each character with the given function
+    ///
+    /// # Errors
+    /// If the lines do not have the same length or a character is rejected an error is returned
+    pub fn parse_with<F>(input: &str, mut parse_cell: F) -> anyhow::Result<Self>
+    where
+        F: FnMut(char) -> anyhow::Result<T>,
+    {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+
+        for (y, line) in input.lines().enumerate() {
+            let start = cells.len();
+
+            for (x, c) in line.chars().enumerate() {
+                cells.push(parse_cell(c).with_context(|| {
+                    format!("invalid cell at line {}, column {}", y + 1, x + 1)
+                })?);
+            }
+
+            let line_width = cells.len() - start;
+            let width = *width.get_or_insert(line_width);
+            ensure!(
+                line_width == width,
+                "line {} has {} cells, expected {}",
+                y + 1,
+                line_width,
+                width
+            );
+        }
+
+        Self::from_cells(width.unwrap_or_default(), cells)
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of cells in the grid
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[self.offset(position)])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            let offset = self.offset(position);
+            Some(&mut self.cells[offset])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn offset(&self, (x, y): Position) -> usize {
+        y * self.width + x
+    }
+
+    #[inline]
+    fn check_bounds(&self, position: Position) {
+        assert!(
+            self.contains(position),
+            "position {:?} is outside of the {}x{} grid",
+            position,
+            self.width,
+            self.height
+        );
+    }
+
+    /// Iterates over every position in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Iterates over every cell with its position in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Iterates over every cell in row-major order
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Mutably iterates over every cell in row-major order
+    pub fn values_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Orthogonal neighbours of a position that are inside the grid
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> {
+        self.offset_positions(position, &ORTHOGONAL)
+    }
+
+    /// Orthogonal and diagonal neighbours of a position that are inside the grid
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> {
+        self.offset_positions(position, &ADJACENT)
+    }
+
+    fn offset_positions(
+        &self,
+        (x, y): Position,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Position> {
+        let (width, height) = (self.width, self.height);
+
+        // Wrapping below zero lands far outside the grid, so a single comparison covers both sides
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+
+            (nx < width && ny < height).then_some((nx, ny))
+        })
+    }
+
+    /// Returns a row of the grid
+    ///
+    /// # Panics
+    /// If the row is outside the grid
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {} is outside the grid", y);
+
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over the rows from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks_exact` panics on zero, an empty grid has no rows anyway
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// Iterates over a column from top to bottom
+    ///
+    /// # Panics
+    /// If the column is outside the grid
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is outside the grid", x);
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Iterates over the columns from left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Returns a window of the grid
+    ///
+    /// # Panics
+    /// If the window does not fit inside the grid
+    #[must_use]
+    pub fn view(&self, origin: Position, width: usize, height: usize) -> GridView<'_, T> {
+        assert!(
+            origin.0 + width <= self.width && origin.1 + height <= self.height,
+            "view of {}x{} at {:?} does not fit in a {}x{} grid",
+            width,
+            height,
+            origin,
+            self.width,
+            self.height
+        );
+
+        GridView {
+            grid: self,
+            origin,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid of the same size by applying a function to every cell
+    #[must_use]
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: From<u8>> Grid<T> {
+    /// Parses a grid of single decimal digits
+    ///
+    /// # Errors
+    /// If the lines do not have the same length or contain anything but digits an error is returned
+    pub fn parse_digits(input: &str) -> anyhow::Result<Self> {
+        Self::parse_with(input, |c| {
+            c.to_digit(10)
+                .map(|digit| T::from(digit as u8))
+                .with_context(|| format!("{:?} is not a digit", c))
+        })
+    }
+}
+
+impl Grid<char> {
+    /// Parses a grid of characters
+    ///
+    /// # Errors
+    /// If the lines do not have the same length an error is returned
+    pub fn parse_chars(input: &str) -> anyhow::Result<Self> {
+        Self::parse_with(input, Ok)
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Returns a cell by its position relative to the view
+    #[must_use]
+    pub fn get(&self, position: Position) -> Option<&'a T> {
+        if self.contains(position) {
+            self.grid.get(self.to_grid(position))
+        } else {
+            None
+        }
+    }
+
+    fn to_grid(&self, (x, y): Position) -> Position {
+        (self.origin.0 + x, self.origin.1 + y)
+    }
+
+    /// Iterates over every position of the view, relative to it, in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Iterates over every cell with its position relative to the view in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &'a T)> + '_ {
+        self.positions()
+            .map(move |position| (position, &self.grid[self.to_grid(position)]))
+    }
+
+    /// Iterates over the rows of the view from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let (grid, (x, y), width) = (self.grid, self.origin, self.width);
+
+        (y..y + self.height).map(move |row| &grid.row(row)[x..x + width])
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, position: Position) -> &Self::Output {
+        self.check_bounds(position);
+
+        &self.cells[self.offset(position)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        self.check_bounds(position);
+
+        let offset = self.offset(position);
+        &mut self.cells[offset]
+    }
+}
+
+impl<T> Index<Position> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside of the {}x{} view",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+fn write_rows<'a, T: Debug + 'a>(
+    f: &mut Formatter<'_>,
+    rows: impl Iterator<Item = &'a [T]>,
+) -> std::fmt::Result {
+    let width = f.width().unwrap_or(0);
+
+    for row in rows {
+        for cell in row {
+            write!(f, "{:>width$?}", cell, width = width)?;
+        }
+        f.write_char('\n')?;
+    }
+
+    Ok(())
+}
+
+/// Prints the grid as a matrix, the formatter width being used for each cell
+impl<T: Debug> Debug for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_rows(f, self.rows())
+    }
+}
+
+impl<T: Debug> Debug for GridView<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_rows(f, self.rows())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = "123\n456";
+
+    fn sample() -> Grid<u8> {
+        Grid::parse_digits(SAMPLE).unwrap()
+    }
+
+    #[test]
+    fn parse_rectangular() {
+        let grid = sample();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 0)], 3);
+        assert_eq!(grid[(0, 1)], 4);
+        assert_eq!(grid.get((3, 0)), None);
+    }
+
+    #[test_case("12\n345" ; "uneven lines")]
+    #[test_case("12\n3x" ; "not a digit")]
+    fn parse_invalid(input: &str) {
+        assert!(Grid::<u8>::parse_digits(input).is_err());
+    }
+
+    #[test_case((0, 0) => vec![(1, 0), (0, 1)]                 ; "corner")]
+    #[test_case((1, 1) => vec![(1, 0), (0, 1), (2, 1)]         ; "bottom edge")]
+    fn neighbours4(position: Position) -> Vec<Position> {
+        sample().neighbours4(position).collect()
+    }
+
+    #[test_case((0, 0) => vec![(1, 0), (0, 1), (1, 1)]                         ; "corner")]
+    #[test_case((1, 0) => vec![(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)]         ; "top edge")]
+    fn neighbours8(position: Position) -> Vec<Position> {
+        sample().neighbours8(position).collect()
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = sample();
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+    }
+
+    #[test]
+    fn view() {
+        let grid = sample();
+        let view = grid.view((1, 0), 2, 2);
+
+        assert_eq!(view[(0, 1)], 5);
+        assert_eq!(view.get((2, 0)), None);
+        assert_eq!(view.rows().collect::<Vec<_>>(), vec![&[2, 3], &[5, 6]]);
+        assert_eq!(format!("{:2?}", view), " 2 3\n 5 6\n");
+    }
+}
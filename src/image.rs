@@ -0,0 +1,376 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Export of the state of the solutions as images
+//!
+//! Grids are exported as raster images, one pixel (or square of pixels) per cell, in either the
+//! binary PPM or the PNG format. Diagrams made of lines are exported as SVG instead, so that large
+//! inputs stay sharp at any zoom level.
+
+use crate::grid::Grid;
+use anyhow::Context;
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Color as its red, green and blue components
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// Picture drawn by a solution
+#[derive(Debug, Clone)]
+pub enum Image {
+    /// Grid of pixels, each cell scaled up to a square of `scale` pixels
+    Raster {
+        pixels: Grid<Rgb>,
+        scale: usize,
+    },
+    Vector(Svg),
+}
+
+/// File format of an exported image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    /// Guesses the format from the extension of a file
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(Self::Ppm),
+            "png" => Ok(Self::Png),
+            "svg" => Ok(Self::Svg),
+            _ => anyhow::bail!(
+                "unknown image format {:?}, expected `ppm`, `png` or `svg`",
+                s
+            ),
+        }
+    }
+}
+
+impl Image {
+    /// Raster image of a grid, coloring each cell with the given map
+    pub fn raster<T>(grid: &Grid<T>, scale: usize, color: impl FnMut(&T) -> Rgb) -> Self {
+        Self::Raster {
+            pixels: grid.map(color),
+            scale: scale.max(1),
+        }
+    }
+
+    /// Writes the image in the given format
+    ///
+    /// # Errors
+    /// An error is returned if the image cannot be written in that format, as vector images are
+    /// only written as SVG and raster images never are, or if writing fails.
+    pub fn write(&self, format: ImageFormat, out: impl Write) -> anyhow::Result<()> {
+        self.check_format(format)?;
+
+        match self {
+            Image::Raster { pixels, scale } if format == ImageFormat::Ppm => {
+                write_ppm(&upscale(pixels, *scale), out)
+            }
+            Image::Raster { pixels, scale } => write_png(&upscale(pixels, *scale), out),
+            Image::Vector(svg) => svg.write(out),
+        }
+    }
+
+    fn check_format(&self, format: ImageFormat) -> anyhow::Result<()> {
+        match (self, format) {
+            (Image::Raster { .. }, ImageFormat::Svg) => {
+                anyhow::bail!("this image is a raster, export it as `ppm` or `png`")
+            }
+            (Image::Vector(_), ImageFormat::Ppm | ImageFormat::Png) => {
+                anyhow::bail!("this image is a diagram, export it as `svg`")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes the image to a file
+    ///
+    /// # Errors
+    /// An error is returned if the file cannot be created or the image cannot be written to it
+    pub fn save(&self, path: &Path, format: ImageFormat) -> anyhow::Result<()> {
+        self.check_format(format)?;
+
+        let mut file = BufWriter::new(
+            File::create(path).with_context(|| format!("creating {}", path.display()))?,
+        );
+
+        self.write(format, &mut file)?;
+
+        file.flush()
+            .with_context(|| format!("writing {}", path.display()))
+    }
+}
+
+/// Grid with every cell repeated as a square of `scale` by `scale` cells
+fn upscale(pixels: &Grid<Rgb>, scale: usize) -> Grid<Rgb> {
+    if scale == 1 {
+        return pixels.clone();
+    }
+
+    let mut scaled = Grid::filled(pixels.width() * scale, pixels.height() * scale, BLACK);
+
+    for (x, y) in scaled.positions() {
+        scaled[(x, y)] = pixels[(x / scale, y / scale)];
+    }
+
+    scaled
+}
+
+/// Writes the pixels as a binary portable pixmap
+fn write_ppm(pixels: &Grid<Rgb>, mut out: impl Write) -> anyhow::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", pixels.width(), pixels.height())?;
+
+    for pixel in pixels.values() {
+        out.write_all(pixel)?;
+    }
+
+    Ok(())
+}
+
+fn write_png(pixels: &Grid<Rgb>, out: impl Write) -> anyhow::Result<()> {
+    let size = |value: usize| u32::try_from(value).context("image is too large for a PNG");
+
+    let mut encoder = png::Encoder::new(out, size(pixels.width())?, size(pixels.height())?);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let data: Vec<u8> = pixels.values().flatten().copied().collect();
+    encoder.write_header()?.write_image_data(&data)?;
+
+    Ok(())
+}
+
+/// Color at a fraction of a gradient from dark blue through green to yellow, clamped to `0..=1`
+pub fn gradient(fraction: f64) -> Rgb {
+    const STOPS: [Rgb; 5] = [
+        [68, 1, 84],
+        [59, 82, 139],
+        [33, 145, 140],
+        [94, 201, 98],
+        [253, 231, 37],
+    ];
+
+    let position = fraction.clamp(0., 1.) * (STOPS.len() - 1) as f64;
+    let index = (position as usize).min(STOPS.len() - 2);
+    let offset = position - index as f64;
+
+    let (from, to) = (STOPS[index], STOPS[index + 1]);
+
+    [0, 1, 2].map(|channel| {
+        let (a, b) = (f64::from(from[channel]), f64::from(to[channel]));
+        (a + (b - a) * offset).round() as u8
+    })
+}
+
+/// Vector drawing in a coordinate space of the caller's choosing, written as SVG
+#[derive(Debug, Clone)]
+pub struct Svg {
+    view_box: (f64, f64, f64, f64),
+    elements: String,
+}
+
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+impl Svg {
+    /// Empty drawing showing the rectangle between the two corners, with a black background
+    pub fn new(min: (f64, f64), max: (f64, f64)) -> Self {
+        let mut svg = Self {
+            view_box: (min.0, min.1, max.0 - min.0, max.1 - min.1),
+            elements: String::new(),
+        };
+
+        svg.rect(min, max, BLACK, 1.);
+        svg
+    }
+
+    /// Width of a stroke relative to the size of the drawing, so it stays visible when scaled
+    pub fn stroke(&self, fraction: f64) -> f64 {
+        self.view_box.2.max(self.view_box.3) * fraction
+    }
+
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), color: Rgb, width: f64, opacity: f64) {
+        let _ = writeln!(
+            self.elements,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}" stroke-opacity="{}" stroke-linecap="round"/>"#,
+            from.0,
+            from.1,
+            to.0,
+            to.1,
+            hex(color),
+            width,
+            opacity
+        );
+    }
+
+    pub fn polyline(&mut self, points: &[(f64, f64)], color: Rgb, width: f64) {
+        let points: Vec<_> = points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+
+        let _ = writeln!(
+            self.elements,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linejoin="round"/>"#,
+            points.join(" "),
+            hex(color),
+            width
+        );
+    }
+
+    /// Filled rectangle between two corners
+    pub fn rect(&mut self, a: (f64, f64), b: (f64, f64), color: Rgb, opacity: f64) {
+        let _ = writeln!(
+            self.elements,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="{}"/>"#,
+            a.0.min(b.0),
+            a.1.min(b.1),
+            (a.0 - b.0).abs(),
+            (a.1 - b.1).abs(),
+            hex(color),
+            opacity
+        );
+    }
+
+    pub fn circle(&mut self, center: (f64, f64), radius: f64, color: Rgb) {
+        let _ = writeln!(
+            self.elements,
+            r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+            center.0,
+            center.1,
+            radius,
+            hex(color)
+        );
+    }
+
+    fn write(&self, mut out: impl Write) -> anyhow::Result<()> {
+        write!(out, "{}", self)?;
+
+        Ok(())
+    }
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (x, y, width, height) = self.view_box;
+
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+            x,
+            y,
+            width,
+            height,
+            width.max(1.),
+            height.max(1.)
+        )?;
+        f.write_str(&self.elements)?;
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    fn checkers() -> Image {
+        let grid = Grid::from_cells(2, vec![true, false, false, true]).unwrap();
+
+        Image::raster(&grid, 2, |&on| if on { WHITE } else { BLACK })
+    }
+
+    #[test]
+    fn ppm() {
+        let mut out = Vec::new();
+        checkers().write(ImageFormat::Ppm, &mut out).unwrap();
+
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&out[..header.len()], header);
+        assert_eq!(out.len(), header.len() + 4 * 4 * 3);
+        // The second row of pixels still belongs to the first row of cells
+        let row = &out[header.len() + 12..header.len() + 24];
+        assert_eq!(row, [[255; 6], [0; 6]].concat());
+    }
+
+    #[test]
+    fn png() {
+        let mut out = Vec::new();
+        checkers().write(ImageFormat::Png, &mut out).unwrap();
+
+        let mut reader = png::Decoder::new(out.as_slice()).read_info().unwrap();
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).unwrap();
+
+        assert_eq!((info.width, info.height), (4, 4));
+        assert_eq!(&buffer[..3], &WHITE);
+        assert_eq!(&buffer[6..9], &BLACK);
+    }
+
+    #[test]
+    fn svg() {
+        let mut svg = Svg::new((0., 0.), (10., 5.));
+        svg.line((0., 0.), (10., 5.), WHITE, 0.5, 1.);
+
+        let rendered = svg.to_string();
+
+        assert!(
+            rendered.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 5""#)
+        );
+        assert!(rendered.contains(r##"<line x1="0" y1="0" x2="10" y2="5" stroke="#ffffff""##));
+        assert!(rendered.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn mismatched_format() {
+        let svg = Image::Vector(Svg::new((0., 0.), (1., 1.)));
+
+        assert!(checkers().write(ImageFormat::Svg, Vec::new()).is_err());
+        assert!(svg.write(ImageFormat::Png, Vec::new()).is_err());
+    }
+
+    #[test_case(0. => [68, 1, 84] ; "start")]
+    #[test_case(1. => [253, 231, 37] ; "end")]
+    #[test_case(2. => [253, 231, 37] ; "clamped")]
+    #[test_case(0.125 => [64, 42, 112] ; "between stops")]
+    fn gradient_stops(fraction: f64) -> Rgb {
+        gradient(fraction)
+    }
+}
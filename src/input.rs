@@ -0,0 +1,278 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Providers for the content of the inputs of each day.
+//!
+//! Inputs can come from the source tree, an explicit directory, the standard input
+//! or be embedded in the binary, and [`ChainedInputProvider`] combines those sources.
+
+use anyhow::Context;
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Name under which [`StdinInputProvider`] serves the standard input
+pub const STDIN_INPUT: &str = "-";
+
+pub trait InputProvider {
+    /// Returns the input as a string
+    ///
+    /// # Errors
+    /// If the input cannot be read or is not valid an error is returned
+    fn get_input(&self, name: &str) -> anyhow::Result<String>;
+}
+
+impl<T: InputProvider + ?Sized> InputProvider for &T {
+    fn get_input(&self, name: &str) -> anyhow::Result<String> {
+        (**self).get_input(name)
+    }
+}
+
+/// Reads the inputs from a directory fixed at compile time, usually the source tree
+pub struct LazyInputProvider(&'static str);
+
+impl LazyInputProvider {
+    #[must_use]
+    pub const fn new(path: &'static str) -> Self {
+        Self(path)
+    }
+}
+
+#[macro_export]
+macro_rules! lazy_input {
+    ($day: literal) => {
+        $crate::input::LazyInputProvider::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/day",
+            $day,
+            "/input"
+        ))
+    };
+}
+
+impl InputProvider for LazyInputProvider {
+    fn get_input(&self, name: &str) -> anyhow::Result<String> {
+        read_file(&Path::new(self.0).join(name))
+    }
+}
+
+/// Reads the inputs from a directory chosen at runtime
+pub struct PathInputProvider(PathBuf);
+
+impl PathInputProvider {
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self(root.into())
+    }
+}
+
+impl InputProvider for PathInputProvider {
+    fn get_input(&self, name: &str) -> anyhow::Result<String> {
+        read_file(&self.0.join(name))
+    }
+}
+
+fn read_file(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read input file {}", path.display()))
+}
+
+/// Serves the standard input under the name [`STDIN_INPUT`].
+///
+/// The standard input is read on the first request and the same content is returned afterwards.
+#[derive(Default)]
+pub struct StdinInputProvider(Mutex<Option<String>>);
+
+impl StdinInputProvider {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+}
+
+impl InputProvider for StdinInputProvider {
+    fn get_input(&self, name: &str) -> anyhow::Result<String> {
+        if name != STDIN_INPUT {
+            return Err(not_found(name));
+        }
+
+        let mut content = self.0.lock().unwrap_or_else(|e| e.into_inner());
+
+        if content.is_none() {
+            *content = Some(read_piped(std::io::stdin())?);
+        }
+
+        Ok(content.clone().unwrap_or_default())
+    }
+}
+
+/// Reads piped content, dropping the newline that ends it like the input files of the source tree
+fn read_piped(mut reader: impl Read) -> anyhow::Result<String> {
+    let mut buffer = String::new();
+    reader
+        .read_to_string(&mut buffer)
+        .context("failed to read the standard input")?;
+
+    if buffer.ends_with('\n') {
+        buffer.pop();
+
+        if buffer.ends_with('\r') {
+            buffer.pop();
+        }
+    }
+
+    Ok(buffer)
+}
+
+/// Serves inputs baked into the binary at compile time
+pub struct EmbeddedInputProvider(&'static [(&'static str, &'static str)]);
+
+impl EmbeddedInputProvider {
+    #[must_use]
+    pub const fn new(files: &'static [(&'static str, &'static str)]) -> Self {
+        Self(files)
+    }
+}
+
+/// Embeds the given files from the input directory of a day
+#[macro_export]
+macro_rules! embedded_input {
+    ($day: literal $(, $name: literal)* $(,)?) => {
+        $crate::input::EmbeddedInputProvider::new(&[$(
+            (
+                $name,
+                include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/src/day",
+                    $day,
+                    "/input/",
+                    $name
+                )),
+            ),
+        )*])
+    };
+}
+
+impl InputProvider for EmbeddedInputProvider {
+    fn get_input(&self, name: &str) -> anyhow::Result<String> {
+        self.0
+            .iter()
+            .find(|&&(file, _)| file == name)
+            .map(|&(_, content)| content.to_string())
+            .ok_or_else(|| not_found(name))
+    }
+}
+
+/// Tries each provider in order, returning the first input found.
+///
+/// Providers that do not have the input are skipped, any other error is returned immediately.
+#[derive(Default)]
+pub struct ChainedInputProvider<'a>(Vec<Box<dyn InputProvider + 'a>>);
+
+impl<'a> ChainedInputProvider<'a> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with(mut self, provider: impl InputProvider + 'a) -> Self {
+        self.0.push(Box::new(provider));
+        self
+    }
+}
+
+impl InputProvider for ChainedInputProvider<'_> {
+    fn get_input(&self, name: &str) -> anyhow::Result<String> {
+        for provider in &self.0 {
+            match provider.get_input(name) {
+                Err(e) if is_not_found(&e) => continue,
+                result => return result,
+            }
+        }
+
+        Err(not_found(name))
+    }
+}
+
+fn not_found(name: &str) -> anyhow::Error {
+    std::io::Error::new(ErrorKind::NotFound, format!("input {} not found", name)).into()
+}
+
+/// Whether an error returned by an [`InputProvider`] means the input does not exist
+#[must_use]
+pub fn is_not_found(error: &anyhow::Error) -> bool {
+    error
+        .root_cause()
+        .downcast_ref::<std::io::Error>()
+        .is_some_and(|e| e.kind() == ErrorKind::NotFound)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FIRST: EmbeddedInputProvider = EmbeddedInputProvider::new(&[("a.txt", "first")]);
+    const SECOND: EmbeddedInputProvider =
+        EmbeddedInputProvider::new(&[("a.txt", "second"), ("b.txt", "second")]);
+
+    #[test]
+    fn chain_returns_first_match() {
+        let chain = ChainedInputProvider::new().with(&FIRST).with(&SECOND);
+
+        assert_eq!(chain.get_input("a.txt").unwrap(), "first");
+        assert_eq!(chain.get_input("b.txt").unwrap(), "second");
+        assert!(is_not_found(&chain.get_input("c.txt").unwrap_err()));
+    }
+
+    #[test]
+    fn chain_skips_missing_files() {
+        let chain = ChainedInputProvider::new()
+            .with(PathInputProvider::new("/nonexistent"))
+            .with(StdinInputProvider::default())
+            .with(&SECOND);
+
+        assert_eq!(chain.get_input("b.txt").unwrap(), "second");
+    }
+
+    #[test]
+    fn piped_input_with_newline() {
+        let day = crate::registry::get_day(16).unwrap();
+        let content = read_piped("D2FE28\r\n".as_bytes()).unwrap();
+
+        assert_eq!(content, "D2FE28");
+        assert!(day.parse(&content).is_ok());
+        assert_eq!(read_piped("1\n2\n\n".as_bytes()).unwrap(), "1\n2\n");
+    }
+
+    #[test]
+    fn embedded_day_inputs() {
+        let provider = embedded_input!(1, "sample.txt");
+
+        assert!(provider
+            .get_input("sample.txt")
+            .unwrap()
+            .starts_with("199\n"));
+    }
+}
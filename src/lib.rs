@@ -24,42 +24,160 @@
 
 //! Common utilities for the challenges
 
-use anyhow::Context;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Display, Formatter};
 use std::ops::Sub;
 
-pub trait InputProvider {
-    /// Returns the input as a string
+pub mod bench;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod nom;
+pub mod registry;
+pub mod report;
+pub mod scaffold;
+pub mod search;
+pub mod verify;
+pub mod viz;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub use input::{InputProvider, LazyInputProvider};
+
+/// Common interface implemented by the solution of every day
+pub trait Solution {
+    /// Parsed representation of the input shared by both challenges
+    type Input;
+
+    /// Parses the raw content of an input file
     ///
     /// # Errors
-    /// If the input cannot be read or is not valid an error is returned
-    fn get_input(&self, name: &str) -> anyhow::Result<String>;
-}
+    /// If the content is not a valid input for the day an error is returned
+    fn parse(&self, content: &str) -> anyhow::Result<Self::Input>;
+
+    /// Solves the first challenge of the day
+    ///
+    /// # Errors
+    /// If the challenge cannot be solved for the given input an error is returned
+    fn challenge_one(&self, input: &Self::Input) -> anyhow::Result<Answer>;
 
-pub struct LazyInputProvider(&'static str);
+    /// Solves the second challenge of the day
+    ///
+    /// # Errors
+    /// If the challenge cannot be solved for the given input an error is returned
+    fn challenge_two(&self, input: &Self::Input) -> anyhow::Result<Answer>;
+
+    /// Animates how the input is solved, for the days that can be visualized
+    fn animate(&self, _input: &Self::Input) -> Option<Box<dyn viz::Animation>> {
+        None
+    }
 
-impl LazyInputProvider {
-    #[must_use]
-    pub const fn new(path: &'static str) -> Self {
-        Self(path)
+    /// Draws the input or its solution, for the days that can be exported as an image
+    fn draw(&self, _input: &Self::Input) -> Option<image::Image> {
+        None
     }
 }
 
-#[macro_export]
-macro_rules! lazy_input {
-    ($day: literal) => {
-        LazyInputProvider::new(concat!(
-            env!("CARGO_MANIFEST_DIR"),
-            "/src/day",
-            $day,
-            "/input"
-        ))
+/// Answer of a challenge, either a number or a free-form text
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+macro_rules! answer_from_number {
+    ($($t: ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Number(i128::from(value))
+                }
+            }
+        )*
     };
 }
 
-impl InputProvider for LazyInputProvider {
-    fn get_input(&self, name: &str) -> anyhow::Result<String> {
-        std::fs::read_to_string(std::path::Path::new(self.0).join(name))
-            .context("failed to read input file")
+answer_from_number!(i32, u32, u64, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Number(value as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AnswerVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for AnswerVisitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                f.write_str("an integer or a string")
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(Answer::Text(v.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Number(n) => serializer.serialize_i128(*n),
+            Answer::Text(text) => serializer.serialize_str(text),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => Display::fmt(n, f),
+            Answer::Text(text) => f.write_str(text),
+        }
     }
 }
 
@@ -106,89 +224,3 @@ where
         }
     }
 }
-
-pub mod nom {
-    use nom::combinator::all_consuming;
-    use nom::{
-        branch::alt,
-        bytes::complete::tag,
-        character::complete::{space0, u32},
-        error::VerboseError,
-        multi::fill,
-        sequence::{delimited, terminated},
-        Finish, IResult, InputLength, Parser,
-    };
-    use std::fmt::Debug;
-
-    pub type ParseResult<'a, T, I = &'a str> = IResult<I, T, VerboseError<I>>;
-
-    /// Parses a u32 and casts it to a usize
-    ///
-    /// # Errors
-    /// If the input is not a valid u32 an error is returned
-    pub fn parse_usize(s: &str) -> nom::IResult<&str, usize, nom::error::VerboseError<&str>> {
-        u32.map(|n| n as usize).parse(s)
-    }
-
-    /// Parses a sequence of N space delimited u32s as an array of usize.
-    /// After the Nth element, the parser completes and returns the remaining with the result, even if it contains more elements.
-    ///
-    /// Uses `parse_usize` internally.
-    ///
-    /// # Errors
-    /// An error is returned if any of:
-    /// - The `parse_usize` fails for any of the elements in the sequence
-    /// - The input does not start with N space-delimited u32s
-    pub fn parse_usize_array<const N: usize>(
-        input: &str,
-    ) -> IResult<&str, [usize; N], VerboseError<&str>> {
-        let mut data = [0; N];
-
-        let result = fill(
-            |input| delimited(space0, parse_usize, space0)(input),
-            &mut data[..],
-        )(input);
-
-        result.map(move |(rem, _)| (rem, data))
-    }
-
-    /// Parses a matrix of N x M space/newline delimited u32s as an array arrays of usize.
-    /// After the Mth line, the parser completes and returns the remaining with the result, even if it contains more elements.
-    /// Each line must contain exactly N elements.
-    ///
-    /// Uses `parse_usize_array` internally.
-    ///
-    /// # Errors
-    /// An error is returned if any of:
-    /// - The `parse_usize_array` fails for any of the lines in the matrix
-    /// - Any line does not contain exactly N elements
-    /// - The input contains less than M lines
-    pub fn parse_usize_matrix<const N: usize, const M: usize>(
-        input: &str,
-    ) -> IResult<&str, [[usize; N]; M], VerboseError<&str>> {
-        let mut data = [[0; N]; M];
-
-        let result = fill(
-            |input| terminated(parse_usize_array, alt((tag("\n"), tag(""))))(input),
-            &mut data[..],
-        )(input);
-
-        result.map(move |(rem, _)| (rem, data))
-    }
-
-    /// Parses an input completely and returns the parsed value.
-    ///
-    /// # Errors
-    /// Any errors that occur during parsing are passed through unchanged.
-    /// If the parser completes, but the input is not fully consumed, an error is returned.
-    pub fn parse_all<I, O, P>(parser: P, data: I) -> anyhow::Result<O>
-    where
-        I: InputLength + Debug,
-        P: Parser<I, O, VerboseError<I>>,
-    {
-        all_consuming(parser)(data)
-            .finish()
-            .map_err(|e| anyhow::anyhow!("{:?}", e))
-            .map(|(_, data)| data)
-    }
-}
@@ -0,0 +1,565 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Runner for the Advent of Code 2021 solutions
+
+use anyhow::Context;
+use aoc2021::bench::{bench_day, Baseline, BenchOptions, BenchTable};
+use aoc2021::day16::{inspect, View};
+use aoc2021::image::ImageFormat;
+use aoc2021::input::STDIN_INPUT;
+use aoc2021::registry::{get_day, Day, DaySelection, Part};
+use aoc2021::report::{Format, Record, Reporter};
+use aoc2021::verify::{verify_day, DiffTable, MANIFEST_NAME};
+use aoc2021::viz::record::{RecordFormat, Recording};
+use aoc2021::viz::Animation;
+use clap::{Parser, Subcommand};
+use log::LevelFilter;
+use std::any::Any;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+#[derive(Parser)]
+#[clap(about = "Runs the Advent of Code 2021 solutions")]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+
+    /// Directory containing a `dayN` folder of inputs for each day, looked up before the bundled inputs
+    #[clap(long, global = true, env = "AOC_INPUT_DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Log diagnostics of the solutions to stderr, repeat for more details. `RUST_LOG` takes precedence
+    #[clap(long, short, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solutions for the selected days
+    Run {
+        /// Days to run: `all`, a day number, a range like `3..7` or a comma-separated list of those
+        days: DaySelection,
+
+        /// Only run the given part of each day
+        #[clap(long, short)]
+        part: Option<Part>,
+
+        /// Name of an input to run, `-` reads it from the standard input. Can be repeated. Defaults to all the inputs of each day
+        #[clap(long = "input", short)]
+        inputs: Vec<String>,
+
+        /// Output format: `text`, `json` for a single array or `ndjson` for one object per line
+        #[clap(long, short, default_value = "text")]
+        format: Format,
+    },
+    /// Check the solutions of the selected days against their expected answers
+    Verify {
+        /// Days to verify: `all`, a day number, a range like `3..7` or a comma-separated list of those
+        days: DaySelection,
+    },
+    /// Time the parsing and each part of the selected days
+    Bench {
+        /// Days to benchmark: `all`, a day number, a range like `3..7` or a comma-separated list of those
+        days: DaySelection,
+
+        /// Only benchmark the given part of each day
+        #[clap(long, short)]
+        part: Option<Part>,
+
+        /// Name of an input to benchmark, `-` reads it from the standard input. Can be repeated. Defaults to all the inputs of each day
+        #[clap(long = "input", short)]
+        inputs: Vec<String>,
+
+        /// Number of untimed runs before measuring
+        #[clap(long, default_value = "3")]
+        warmup: u32,
+
+        /// Number of timed runs
+        #[clap(long, short = 'n', default_value = "10")]
+        iterations: u32,
+
+        /// Baseline to compare the results against
+        #[clap(long)]
+        baseline: Option<PathBuf>,
+
+        /// Maximum slowdown of the median, in percent, before a stage is reported as a regression
+        #[clap(long, default_value = "10")]
+        threshold: f64,
+
+        /// Save the results as a baseline, merging them into the file if it already exists
+        #[clap(long)]
+        save: Option<PathBuf>,
+    },
+    /// Create the skeleton of a new day and register it
+    Scaffold {
+        /// Number of the day to create
+        day: u8,
+
+        /// Overwrite the sources of an unregistered day, registered days and inputs are always kept
+        #[clap(long)]
+        force: bool,
+
+        /// Root of the crate to create the day in
+        #[clap(long, default_value = env!("CARGO_MANIFEST_DIR"))]
+        root: PathBuf,
+    },
+    /// Animate the simulation of a day in the terminal
+    Viz {
+        /// Number of the day to animate, only some days can be visualized
+        day: u8,
+
+        /// Name of the input to animate, `-` reads it from the standard input. Defaults to the last input of the day
+        #[clap(long, short)]
+        input: Option<String>,
+
+        /// Initial playback speed in frames per second
+        #[clap(long, default_value = "10")]
+        fps: u32,
+    },
+    /// Record the animation of a day to an asciicast or GIF file
+    Record {
+        /// Number of the day to record, only some days can be visualized
+        day: u8,
+
+        /// File to write the recording to
+        #[clap(long, short)]
+        output: PathBuf,
+
+        /// Name of the input to animate, `-` reads it from the standard input. Defaults to the last input of the day
+        #[clap(long, short)]
+        input: Option<String>,
+
+        /// Format of the recording: `cast` or `gif`. Defaults to the extension of the output
+        #[clap(long, short)]
+        format: Option<RecordFormat>,
+
+        /// Playback speed in frames per second
+        #[clap(long, default_value = "10")]
+        fps: u32,
+
+        /// Maximum number of frames to record, as some animations never end
+        #[clap(long, default_value = "1000")]
+        limit: usize,
+    },
+    /// Draw the state of a day as a PPM, PNG or SVG image
+    Export {
+        /// Number of the day to draw, only some days can be drawn
+        day: u8,
+
+        /// File to write the image to
+        #[clap(long, short)]
+        output: PathBuf,
+
+        /// Name of the input to draw, `-` reads it from the standard input. Defaults to the last input of the day
+        #[clap(long, short)]
+        input: Option<String>,
+
+        /// Format of the image: `ppm`, `png` or `svg`. Defaults to the extension of the output
+        #[clap(long, short)]
+        format: Option<ImageFormat>,
+    },
+    /// Disassemble a BITS transmission of day 16 or show it as an expression
+    Bits {
+        /// Hexadecimal transmission to inspect. Defaults to an input of day 16
+        transmission: Option<String>,
+
+        /// Name of the input to inspect when no transmission is given, `-` reads it from the standard input
+        #[clap(long, short, default_value = "input")]
+        input: String,
+
+        /// How to show the transmission: `disasm`, `infix`, `sexpr`, `value` or `trace`
+        #[clap(long, default_value = "disasm")]
+        view: View,
+    },
+}
+
+/// Inputs to run for a day, falling back to its defaults when none are requested
+fn day_inputs(day: &Day, inputs: &[String]) -> Vec<String> {
+    if inputs.is_empty() {
+        day.default_inputs
+            .iter()
+            .map(|&name| name.to_string())
+            .collect()
+    } else {
+        inputs.to_vec()
+    }
+}
+
+fn run(
+    days: &DaySelection,
+    input_dir: Option<&Path>,
+    part: Option<Part>,
+    inputs: &[String],
+    format: Format,
+) -> anyhow::Result<()> {
+    let parts = part.as_ref().map_or(&Part::ALL[..], std::slice::from_ref);
+    let mut reporter = Reporter::new(format, std::io::stdout().lock());
+    let mut failures = 0;
+
+    for day in days.days() {
+        let provider = day.provider(input_dir);
+
+        for input in &day_inputs(day, inputs) {
+            let parsed = day
+                .read_input(&provider, input)
+                .and_then(|content| day.parse(&content));
+
+            for &part in parts {
+                let record = match &parsed {
+                    Ok(parsed) => {
+                        let start = Instant::now();
+                        let result = day.solve(part, parsed.as_ref());
+                        let duration = start.elapsed();
+
+                        Record::new(
+                            day.number,
+                            input,
+                            part,
+                            result.as_ref().cloned(),
+                            Some(duration),
+                        )
+                    }
+                    Err(error) => Record::new(day.number, input, part, Err(error), None),
+                };
+
+                if !record.is_success() {
+                    failures += 1;
+                }
+
+                reporter.report(record)?;
+            }
+        }
+    }
+
+    reporter.finish()?;
+
+    anyhow::ensure!(failures == 0, "{} run(s) failed", failures);
+
+    Ok(())
+}
+
+fn verify(days: &DaySelection, input_dir: Option<&Path>) -> anyhow::Result<()> {
+    let mut outcomes = Vec::new();
+
+    for day in days.days() {
+        match verify_day(day, &day.provider(input_dir))? {
+            Some(day_outcomes) => {
+                let passed = day_outcomes.iter().filter(|o| o.is_success()).count();
+
+                println!(
+                    "Day {}: {}/{} answers match",
+                    day.number,
+                    passed,
+                    day_outcomes.len()
+                );
+
+                outcomes.extend(day_outcomes);
+            }
+            None => println!("Day {}: no {} found, skipping", day.number, MANIFEST_NAME),
+        }
+    }
+
+    let failures = outcomes.iter().filter(|o| !o.is_success()).count();
+
+    if failures > 0 {
+        println!("\n{}", DiffTable(&outcomes));
+        anyhow::bail!("{} answer(s) do not match", failures);
+    }
+
+    Ok(())
+}
+
+struct BenchArgs {
+    parts: Vec<Part>,
+    inputs: Vec<String>,
+    options: BenchOptions,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    save: Option<PathBuf>,
+}
+
+fn bench(days: &DaySelection, input_dir: Option<&Path>, args: &BenchArgs) -> anyhow::Result<()> {
+    let mut measurements = Vec::new();
+    let mut failures = 0;
+
+    for day in days.days() {
+        let provider = day.provider(input_dir);
+
+        for input in &day_inputs(day, &args.inputs) {
+            match bench_day(day, &provider, input, &args.parts, args.options) {
+                Ok(day_measurements) => measurements.extend(day_measurements),
+                Err(error) => {
+                    failures += 1;
+                    eprintln!("Day {} ({}) failed: {:#}", day.number, input, error);
+                }
+            }
+        }
+    }
+
+    let baseline = args.baseline.as_deref().map(Baseline::load).transpose()?;
+
+    println!(
+        "{}",
+        BenchTable {
+            measurements: &measurements,
+            baseline: baseline.as_ref(),
+        }
+    );
+
+    if let Some(path) = &args.save {
+        let mut saved = if path.exists() {
+            Baseline::load(path)?
+        } else {
+            Baseline::default()
+        };
+
+        saved.record(&measurements);
+        saved.save(path)?;
+    }
+
+    anyhow::ensure!(failures == 0, "{} benchmark(s) failed", failures);
+
+    if let Some(baseline) = &baseline {
+        let regressions = baseline.regressions(&measurements, args.threshold);
+
+        for regression in &regressions {
+            eprintln!(
+                "Regression in {}: median {:.2?} -> {:.2?} ({:+.1}%)",
+                regression.measurement.key(),
+                regression.baseline.median,
+                regression.measurement.stats.median,
+                regression.change
+            );
+        }
+
+        anyhow::ensure!(
+            regressions.is_empty(),
+            "{} stage(s) regressed beyond {}%",
+            regressions.len(),
+            args.threshold
+        );
+    }
+
+    Ok(())
+}
+
+fn scaffold(day: u8, force: bool, root: &Path) -> anyhow::Result<()> {
+    for path in aoc2021::scaffold::scaffold(root, day, force)? {
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Parses an input of a day for drawing it, defaulting to the last input of the day
+fn parse_for_drawing(
+    day: u8,
+    input: Option<&str>,
+    input_dir: Option<&Path>,
+) -> anyhow::Result<(&'static Day, String, Box<dyn Any>)> {
+    let day = get_day(day).with_context(|| format!("day {} does not exist", day))?;
+    let input = input
+        .or_else(|| day.default_inputs.last().copied())
+        .with_context(|| format!("day {} has no inputs", day.number))?;
+
+    let content = day.read_input(&day.provider(input_dir), input)?;
+    let parsed = day.parse(&content)?;
+
+    Ok((day, format!("Day {} ({})", day.number, input), parsed))
+}
+
+/// Animation of a day along with its title
+fn animation(
+    day: u8,
+    input: Option<&str>,
+    input_dir: Option<&Path>,
+) -> anyhow::Result<(String, Box<dyn Animation>)> {
+    let (day, title, parsed) = parse_for_drawing(day, input, input_dir)?;
+
+    Ok((title, day.animate(parsed.as_ref())?))
+}
+
+fn viz(day: u8, input: Option<&str>, input_dir: Option<&Path>, fps: u32) -> anyhow::Result<()> {
+    let (title, animation) = animation(day, input, input_dir)?;
+
+    aoc2021::viz::play(&title, animation, fps)
+}
+
+struct RecordArgs {
+    output: PathBuf,
+    input: Option<String>,
+    format: Option<RecordFormat>,
+    fps: u32,
+    limit: usize,
+}
+
+fn record(day: u8, input_dir: Option<&Path>, args: &RecordArgs) -> anyhow::Result<()> {
+    let format = args
+        .format
+        .or_else(|| RecordFormat::from_path(&args.output))
+        .context("cannot guess the format from the output, pass `--format`")?;
+
+    let (title, mut animation) = animation(day, args.input.as_deref(), input_dir)?;
+    let recording = Recording::capture(&title, animation.as_mut(), args.fps, args.limit);
+
+    if recording.frames.len() == args.limit {
+        log::warn!(
+            "Stopped recording at the limit of {} frames, raise `--limit` to record more",
+            args.limit
+        );
+    }
+
+    recording.save(&args.output, format)?;
+
+    println!(
+        "Recorded {} frames to {}",
+        recording.frames.len(),
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+fn export(
+    day: u8,
+    input: Option<&str>,
+    input_dir: Option<&Path>,
+    output: &Path,
+    format: Option<ImageFormat>,
+) -> anyhow::Result<()> {
+    let format = format
+        .or_else(|| ImageFormat::from_path(output))
+        .context("cannot guess the format from the output, pass `--format`")?;
+
+    let (day, _, parsed) = parse_for_drawing(day, input, input_dir)?;
+
+    day.draw(parsed.as_ref())?.save(output, format)?;
+
+    println!("Wrote {}", output.display());
+
+    Ok(())
+}
+
+fn bits(
+    transmission: Option<&str>,
+    input: &str,
+    input_dir: Option<&Path>,
+    view: View,
+) -> anyhow::Result<()> {
+    // The standard output is line buffered, so each line shows up as soon as it is decoded
+    let mut output = std::io::stdout().lock();
+
+    match transmission {
+        Some(transmission) => inspect(transmission.as_bytes(), view, &mut output)?,
+        // Stream the standard input instead of reading it whole
+        None if input == STDIN_INPUT => inspect(std::io::stdin().lock(), view, &mut output)?,
+        None => {
+            let day = &aoc2021::day16::DAY;
+            let content = day.read_input(&day.provider(input_dir), input)?;
+            inspect(content.as_bytes(), view, &mut output)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let level = match cli.verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .init();
+
+    let input_dir = cli.input_dir.as_deref();
+
+    match cli.command {
+        Command::Run {
+            days,
+            part,
+            inputs,
+            format,
+        } => run(&days, input_dir, part, &inputs, format),
+        Command::Verify { days } => verify(&days, input_dir),
+        Command::Bench {
+            days,
+            part,
+            inputs,
+            warmup,
+            iterations,
+            baseline,
+            threshold,
+            save,
+        } => bench(
+            &days,
+            input_dir,
+            &BenchArgs {
+                parts: part.map_or_else(|| Part::ALL.to_vec(), |part| vec![part]),
+                inputs,
+                options: BenchOptions { warmup, iterations },
+                baseline,
+                threshold,
+                save,
+            },
+        ),
+        Command::Scaffold { day, force, root } => scaffold(day, force, &root),
+        Command::Viz { day, input, fps } => viz(day, input.as_deref(), input_dir, fps),
+        Command::Record {
+            day,
+            output,
+            input,
+            format,
+            fps,
+            limit,
+        } => record(
+            day,
+            input_dir,
+            &RecordArgs {
+                output,
+                input,
+                format,
+                fps,
+                limit,
+            },
+        ),
+        Command::Export {
+            day,
+            output,
+            input,
+            format,
+        } => export(day, input.as_deref(), input_dir, &output, format),
+        Command::Bits {
+            transmission,
+            input,
+            view,
+        } => bits(transmission.as_deref(), &input, input_dir, view),
+    }
+}
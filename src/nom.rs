@@ -0,0 +1,381 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Shared nom parsers and helpers to run them over a whole input.
+
+use nom::combinator::{all_consuming, cut};
+use nom::error::{context, ErrorKind, VerboseError, VerboseErrorKind};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, space0, u32},
+    multi::fill,
+    sequence::{delimited, terminated},
+    Finish, IResult, Offset, Parser,
+};
+use std::fmt::Write;
+
+pub type ParseResult<'a, T, I = &'a str> = IResult<I, T, VerboseError<I>>;
+
+/// Bit-level input, as used by [`nom::bits`]: the remaining bytes and the offset in the first one
+pub type BitInput<'a> = (&'a [u8], usize);
+
+/// Parses a u32 and casts it to a usize
+///
+/// # Errors
+/// If the input is not a valid u32 an error is returned
+pub fn parse_usize(s: &str) -> nom::IResult<&str, usize, nom::error::VerboseError<&str>> {
+    u32.map(|n| n as usize).parse(s)
+}
+
+/// Parses a sequence of N space delimited u32s as an array of usize.
+/// After the Nth element, the parser completes and returns the remaining with the result, even if it contains more elements.
+///
+/// Uses `parse_usize` internally.
+///
+/// # Errors
+/// An error is returned if any of:
+/// - The `parse_usize` fails for any of the elements in the sequence
+/// - The input does not start with N space-delimited u32s
+pub fn parse_usize_array<const N: usize>(
+    input: &str,
+) -> IResult<&str, [usize; N], VerboseError<&str>> {
+    let mut data = [0; N];
+
+    let result = fill(
+        |input| delimited(space0, parse_usize, space0)(input),
+        &mut data[..],
+    )(input);
+
+    result.map(move |(rem, _)| (rem, data))
+}
+
+/// Parses a matrix of N x M space/newline delimited u32s as an array arrays of usize.
+/// After the Mth line, the parser completes and returns the remaining with the result, even if it contains more elements.
+/// Each line must contain exactly N elements.
+///
+/// Uses `parse_usize_array` internally.
+///
+/// # Errors
+/// An error is returned if any of:
+/// - The `parse_usize_array` fails for any of the lines in the matrix
+/// - Any line does not contain exactly N elements
+/// - The input contains less than M lines
+pub fn parse_usize_matrix<const N: usize, const M: usize>(
+    input: &str,
+) -> IResult<&str, [[usize; N]; M], VerboseError<&str>> {
+    let mut data = [[0; N]; M];
+
+    let result = fill(
+        |input| terminated(parse_usize_array, alt((tag("\n"), tag(""))))(input),
+        &mut data[..],
+    )(input);
+
+    result.map(move |(rem, _)| (rem, data))
+}
+
+/// Parses one element per line until the end of the input or a blank line.
+///
+/// Unlike a plain `separated_list1`, a line that does not parse is a failure instead of the end
+/// of the list, so the error points at that line rather than at the end of the previous one.
+///
+/// # Errors
+/// An error is returned if the first line does not parse, and a failure if any later line does
+pub fn lines<'a, O, P>(mut parser: P) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>>
+where
+    P: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    move |input: &'a str| {
+        let (mut rest, first) = context("line", |i| parser.parse(i))(input)?;
+        let mut elements = vec![first];
+
+        loop {
+            let next = match line_ending::<_, VerboseError<&str>>(rest) {
+                Ok((next, _)) if !next.is_empty() && line_ending::<_, ()>(next).is_err() => next,
+                _ => return Ok((rest, elements)),
+            };
+
+            let (after, element) = context("line", cut(|i| parser.parse(i)))(next)?;
+
+            elements.push(element);
+            rest = after;
+        }
+    }
+}
+
+/// Parses an input completely and returns the parsed value.
+///
+/// # Errors
+/// If parsing fails, or completes without consuming the whole input, an error is returned
+/// pointing at the line and column of the failure, followed by the stack of contexts being parsed.
+pub fn parse_all<'a, O, P>(parser: P, data: &'a str) -> anyhow::Result<O>
+where
+    P: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    all_consuming(parser)(data)
+        .finish()
+        .map_err(|e| anyhow::anyhow!("{}", render_error(data, &e)))
+        .map(|(_, data)| data)
+}
+
+/// Parses a binary input completely at the bit level and returns the parsed value.
+///
+/// Bits left over in the last partially consumed byte are considered padding and ignored.
+///
+/// # Errors
+/// If parsing fails, or whole bytes are left after it completes, an error is returned
+/// pointing at the bit offset of the failure, followed by the stack of contexts being parsed.
+pub fn parse_all_bits<'a, O, P>(mut parser: P, data: &'a [u8]) -> anyhow::Result<O>
+where
+    P: Parser<BitInput<'a>, O, VerboseError<BitInput<'a>>>,
+{
+    let error = match parser.parse((data, 0)).finish() {
+        Ok(((rest, offset), value)) => {
+            let rest = if offset > 0 { &rest[1..] } else { rest };
+
+            if rest.is_empty() {
+                return Ok(value);
+            }
+
+            VerboseError {
+                errors: vec![((rest, 0), VerboseErrorKind::Nom(ErrorKind::Eof))],
+            }
+        }
+        Err(error) => error,
+    };
+
+    anyhow::bail!("{}", render_bits_error(data, &error))
+}
+
+fn describe(kind: &VerboseErrorKind, found: Option<String>) -> String {
+    let found = found.map_or_else(
+        || "end of input".to_string(),
+        |found| format!("'{}'", found),
+    );
+
+    match kind {
+        VerboseErrorKind::Char(c) => format!("expected '{}', found {}", c, found),
+        VerboseErrorKind::Nom(ErrorKind::Eof) => format!("expected end of input, found {}", found),
+        VerboseErrorKind::Nom(kind) => format!("{} failed at {}", kind.description(), found),
+        VerboseErrorKind::Context(context) => format!("in {}", context),
+    }
+}
+
+/// Line number, column and content of the line at the given offset, both numbers starting at 1
+fn locate(data: &str, offset: usize) -> (usize, usize, &str) {
+    let before = &data[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line_end = data[offset..].find('\n').map_or(data.len(), |i| offset + i);
+
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+        &data[line_start..line_end],
+    )
+}
+
+/// Renders a parse error with the failing line, a caret under the failure and the context stack:
+///
+/// ```text
+/// line 2, column 4: expected ',', found ' '
+///   2 | 0,9 -> 5,9
+///     |    ^
+///   in line segment (line 2, column 1)
+/// ```
+fn render_error(data: &str, error: &VerboseError<&str>) -> String {
+    let mut output = String::new();
+
+    for (index, (input, kind)) in error.errors.iter().enumerate() {
+        let offset = data.offset(input);
+        let (line, column, content) = locate(data, offset);
+
+        if index == 0 {
+            let found = input.chars().next().map(|c| c.escape_debug().to_string());
+            let number = line.to_string();
+
+            let _ = write!(
+                output,
+                "line {}, column {}: {}\n  {} | {}\n  {} | {:>width$}",
+                line,
+                column,
+                describe(kind, found),
+                number,
+                content,
+                " ".repeat(number.len()),
+                "^",
+                width = column,
+            );
+        } else {
+            let _ = write!(
+                output,
+                "\n  {} (line {}, column {})",
+                describe(kind, None),
+                line,
+                column
+            );
+        }
+    }
+
+    output
+}
+
+/// Renders a bit-level parse error with the bit offset of the failure and the context stack:
+///
+/// ```text
+/// bit 22 (byte 2, bit 6): expected end of input, found '0'
+///   ...10111000 00000000
+///             ^
+///   in operator packet (bit 6)
+/// ```
+fn render_bits_error(data: &[u8], error: &VerboseError<BitInput>) -> String {
+    let position = |(rest, offset): BitInput| (data.len() - rest.len()) * 8 + offset;
+    let mut output = String::new();
+
+    for (index, &(input, ref kind)) in error.errors.iter().enumerate() {
+        let bit = position(input);
+
+        if index == 0 {
+            let found = data
+                .get(bit / 8)
+                .map(|byte| ((byte >> (7 - bit % 8)) & 1).to_string());
+
+            // Show the byte of the failure along with its neighbours
+            let first = (bit / 8).saturating_sub(1);
+            let window = data
+                .iter()
+                .skip(first)
+                .take(3)
+                .map(|byte| format!("{:08b}", byte))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let prefix = if first > 0 { "..." } else { "" };
+            let caret = prefix.len() + (bit - first * 8) + (bit / 8 - first) + 1;
+
+            let _ = write!(
+                output,
+                "bit {} (byte {}, bit {}): {}\n  {}{}\n  {:>width$}",
+                bit,
+                bit / 8,
+                bit % 8,
+                describe(kind, found),
+                prefix,
+                window,
+                "^",
+                width = caret,
+            );
+        } else {
+            let _ = write!(output, "\n  {} (bit {})", describe(kind, None), bit);
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nom::character::complete::char;
+    use nom::error::context;
+    use nom::multi::separated_list1;
+    use nom::sequence::separated_pair;
+
+    fn pair(input: &str) -> ParseResult<'_, (usize, usize)> {
+        context("pair", separated_pair(parse_usize, char(','), parse_usize))(input)
+    }
+
+    fn pairs(input: &str) -> ParseResult<'_, Vec<(usize, usize)>> {
+        separated_list1(char('\n'), pair)(input)
+    }
+
+    #[test]
+    fn render_str_error() {
+        let error = parse_all(pair, "12;4").unwrap_err().to_string();
+
+        assert_eq!(
+            error,
+            "line 1, column 3: expected ',', found ';'\n  1 | 12;4\n    |   ^\n  in pair (line 1, column 1)"
+        );
+    }
+
+    #[test]
+    fn render_bad_line() {
+        let error = parse_all(lines(pair), "1,2\n3,4\n5;6\n7,8")
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!(
+            error,
+            "line 3, column 2: expected ',', found ';'\n  3 | 5;6\n    |  ^\n  in pair (line 3, column 1)\n  in line (line 3, column 1)"
+        );
+    }
+
+    #[test]
+    fn lines_stop_at_blank_line() {
+        let (rest, parsed) = lines(pair)("1,2\n3,4\n\n5,6").unwrap();
+
+        assert_eq!(parsed, vec![(1, 2), (3, 4)]);
+        assert_eq!(rest, "\n\n5,6");
+        assert_eq!(lines(pair)("1,2\n").unwrap(), ("\n", vec![(1, 2)]));
+    }
+
+    #[test]
+    fn render_trailing_input() {
+        let error = parse_all(pairs, "1,2\n3,4\n5,6 x").unwrap_err().to_string();
+
+        assert_eq!(
+            error,
+            "line 3, column 4: expected end of input, found ' '\n  3 | 5,6 x\n    |    ^"
+        );
+    }
+
+    fn nibbles(input: BitInput<'_>) -> ParseResult<'_, (u8, u8), BitInput<'_>> {
+        context(
+            "nibbles",
+            nom::sequence::pair(
+                nom::bits::complete::take(4_usize),
+                nom::bits::complete::tag(0b1111, 4_usize),
+            ),
+        )(input)
+    }
+
+    #[test]
+    fn render_bits_error() {
+        let error = parse_all_bits(nibbles, &[0b1010_0111])
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!(
+            error,
+            "bit 4 (byte 0, bit 4): Tag on bitstream failed at '0'\n  10100111\n      ^\n  in nibbles (bit 0)"
+        );
+    }
+
+    #[test]
+    fn bits_ignore_padding() {
+        let nibble = nom::bits::complete::take::<_, u8, _, VerboseError<_>>(4_usize);
+
+        assert_eq!(parse_all_bits(nibble, &[0b1010_0000]).unwrap(), 10);
+        assert!(parse_all_bits(nibbles, &[0b1010_1111, 0]).is_err());
+    }
+}
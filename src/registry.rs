@@ -0,0 +1,353 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Registry of all the days that can be run
+
+use crate::image::Image;
+use crate::input::{
+    ChainedInputProvider, EmbeddedInputProvider, InputProvider, LazyInputProvider,
+    PathInputProvider, StdinInputProvider, STDIN_INPUT,
+};
+use crate::viz::Animation;
+use crate::{Answer, Solution};
+use anyhow::{bail, Context};
+use std::any::Any;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Type-erased [`Solution`], allowing days with different inputs to be handled uniformly.
+pub trait DynSolution: Sync {
+    /// Parses the raw content of an input into the solution's own input type
+    ///
+    /// # Errors
+    /// If the content is not a valid input for the day an error is returned
+    fn parse(&self, content: &str) -> anyhow::Result<Box<dyn Any>>;
+
+    /// Solves a part of the challenge for an input returned by [`DynSolution::parse`]
+    ///
+    /// # Errors
+    /// If the input was not parsed by this solution or the challenge fails an error is returned
+    fn solve(&self, part: Part, input: &dyn Any) -> anyhow::Result<Answer>;
+
+    /// Animates an input returned by [`DynSolution::parse`], if the solution supports it
+    ///
+    /// # Errors
+    /// If the input was not parsed by this solution an error is returned
+    fn animate(&self, input: &dyn Any) -> anyhow::Result<Option<Box<dyn Animation>>>;
+
+    /// Draws an input returned by [`DynSolution::parse`], if the solution supports it
+    ///
+    /// # Errors
+    /// If the input was not parsed by this solution an error is returned
+    fn draw(&self, input: &dyn Any) -> anyhow::Result<Option<Image>>;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn parse(&self, content: &str) -> anyhow::Result<Box<dyn Any>> {
+        Ok(Box::new(Solution::parse(self, content)?))
+    }
+
+    fn solve(&self, part: Part, input: &dyn Any) -> anyhow::Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .context("input was parsed by a different solution")?;
+
+        match part {
+            Part::One => self.challenge_one(input),
+            Part::Two => self.challenge_two(input),
+        }
+    }
+
+    fn animate(&self, input: &dyn Any) -> anyhow::Result<Option<Box<dyn Animation>>> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .context("input was parsed by a different solution")?;
+
+        Ok(Solution::animate(self, input))
+    }
+
+    fn draw(&self, input: &dyn Any) -> anyhow::Result<Option<Image>> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .context("input was parsed by a different solution")?;
+
+        Ok(Solution::draw(self, input))
+    }
+}
+
+pub struct Day {
+    pub number: u8,
+    pub inputs: LazyInputProvider,
+    /// Inputs baked into the binary, used when the source tree is not available
+    pub embedded: EmbeddedInputProvider,
+    /// Names of the inputs that are run when none is explicitly requested
+    pub default_inputs: &'static [&'static str],
+    pub solution: &'static dyn DynSolution,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+static STDIN: StdinInputProvider = StdinInputProvider::new();
+
+impl Day {
+    /// Provider for the files of this day, looking them up in order from:
+    /// - the standard input, for the input named [`STDIN_INPUT`]
+    /// - the `dayN` directory under `root`, when given
+    /// - the source tree
+    /// - the inputs embedded in the binary
+    #[must_use]
+    pub fn provider(&self, root: Option<&Path>) -> ChainedInputProvider<'_> {
+        let mut provider = ChainedInputProvider::new().with(&STDIN);
+
+        if let Some(root) = root {
+            provider = provider.with(PathInputProvider::new(
+                root.join(format!("day{}", self.number)),
+            ));
+        }
+
+        provider.with(&self.inputs).with(&self.embedded)
+    }
+
+    /// Reads the named input of this day, `-` being the standard input
+    ///
+    /// # Errors
+    /// If the input cannot be read an error is returned
+    pub fn read_input(&self, inputs: &impl InputProvider, name: &str) -> anyhow::Result<String> {
+        let file = if name == STDIN_INPUT {
+            name.to_string()
+        } else {
+            format!("{}.txt", name)
+        };
+
+        inputs.get_input(&file).context("reading content")
+    }
+
+    /// Parses the content of an input for this day
+    ///
+    /// # Errors
+    /// If the content is not a valid input an error is returned
+    pub fn parse(&self, content: &str) -> anyhow::Result<Box<dyn Any>> {
+        self.solution.parse(content).context("parsing input")
+    }
+
+    /// Runs the given part of the challenge against an input returned by [`Day::parse`].
+    ///
+    /// # Errors
+    /// If the challenge fails an error is returned
+    pub fn solve(&self, part: Part, input: &dyn Any) -> anyhow::Result<Answer> {
+        self.solution
+            .solve(part, input)
+            .with_context(|| format!("challenge {}", part.name()))
+    }
+
+    /// Animates an input returned by [`Day::parse`]
+    ///
+    /// # Errors
+    /// If the day cannot be visualized an error is returned
+    pub fn animate(&self, input: &dyn Any) -> anyhow::Result<Box<dyn Animation>> {
+        self.solution
+            .animate(input)?
+            .with_context(|| format!("day {} has no visualization", self.number))
+    }
+
+    /// Draws an input returned by [`Day::parse`]
+    ///
+    /// # Errors
+    /// If the day cannot be drawn an error is returned
+    pub fn draw(&self, input: &dyn Any) -> anyhow::Result<Image> {
+        self.solution
+            .draw(input)?
+            .with_context(|| format!("day {} cannot be drawn", self.number))
+    }
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Part::One => "one",
+            Part::Two => "two",
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            _ => bail!("invalid part {}, expected 1 or 2", s),
+        }
+    }
+}
+
+pub static DAYS: &[&Day] = &[
+    &crate::day1::DAY,
+    &crate::day2::DAY,
+    &crate::day3::DAY,
+    &crate::day4::DAY,
+    &crate::day5::DAY,
+    &crate::day6::DAY,
+    &crate::day7::DAY,
+    &crate::day8::DAY,
+    &crate::day9::DAY,
+    &crate::day10::DAY,
+    &crate::day11::DAY,
+    &crate::day12::DAY,
+    &crate::day13::DAY,
+    &crate::day14::DAY,
+    &crate::day15::DAY,
+    &crate::day16::DAY,
+    &crate::day17::DAY,
+    &crate::day18::DAY,
+    &crate::day19::DAY,
+    &crate::day20::DAY,
+    &crate::day21::DAY,
+    &crate::day22::DAY,
+    &crate::day23::DAY,
+    &crate::day24::DAY,
+    &crate::day25::DAY,
+];
+
+/// Returns the registered day with the given number, if any
+#[must_use]
+pub fn get_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().copied().find(|day| day.number == number)
+}
+
+/// Selection of days parsed from the command line.
+///
+/// Accepts `all`, a single day (`14`), an inclusive range (`3..7` or `3-7`)
+/// or a comma-separated list of those (`1,3..5,9`).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DaySelection(Vec<u8>);
+
+impl DaySelection {
+    pub fn days(&self) -> impl Iterator<Item = &'static Day> + '_ {
+        self.0.iter().filter_map(|&number| get_day(number))
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Self(DAYS.iter().map(|day| day.number).collect()));
+        }
+
+        let mut days = Vec::new();
+
+        for part in s.split(',') {
+            let range = part.split_once("..").or_else(|| part.split_once('-'));
+
+            let (start, end): (u8, u8) = match range {
+                Some((start, end)) => (
+                    start.trim().parse().context("invalid range start")?,
+                    end.trim().parse().context("invalid range end")?,
+                ),
+                None => {
+                    let day = part.trim().parse().context("invalid day")?;
+                    (day, day)
+                }
+            };
+
+            if start > end {
+                bail!("range {} is reversed", part.trim());
+            }
+
+            for day in start..=end {
+                if get_day(day).is_none() {
+                    bail!("day {} is not registered", day);
+                }
+
+                if !days.contains(&day) {
+                    days.push(day);
+                }
+            }
+        }
+
+        Ok(Self(days))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("14"        => vec![14]            ; "single day"   )]
+    #[test_case("3..6"      => vec![3, 4, 5, 6]    ; "dotted range" )]
+    #[test_case("3-6"       => vec![3, 4, 5, 6]    ; "dashed range" )]
+    #[test_case("1,3..4,1"  => vec![1, 3, 4]       ; "list"         )]
+    fn parse_selection(input: &str) -> Vec<u8> {
+        input.parse::<DaySelection>().unwrap().0
+    }
+
+    #[test]
+    fn parse_all() {
+        let selection: DaySelection = "all".parse().unwrap();
+
+        assert_eq!(selection.days().count(), DAYS.len());
+    }
+
+    #[test_case("0"    ; "day zero"     )]
+    #[test_case("26"   ; "unknown day"  )]
+    #[test_case("x..3" ; "invalid range")]
+    #[test_case("7..3" ; "reversed range")]
+    fn parse_invalid_selection(input: &str) {
+        assert!(input.parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn solve_rejects_foreign_input() {
+        let (day1, day2) = (get_day(1).unwrap(), get_day(2).unwrap());
+        let input = day1.parse("1\n2\n3").unwrap();
+
+        assert!(day2.solve(Part::One, input.as_ref()).is_err());
+        assert_eq!(
+            day1.solve(Part::One, input.as_ref()).unwrap(),
+            Answer::Number(2)
+        );
+    }
+
+    #[test]
+    fn registry_is_ordered() {
+        for (index, day) in DAYS.iter().enumerate() {
+            assert_eq!(usize::from(day.number), index + 1);
+        }
+    }
+}
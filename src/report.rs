@@ -0,0 +1,261 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Reporting of the answers of the runner, either as text or as JSON for other tools.
+//!
+//! Each record is one part of a day run against one input:
+//!
+//! ```json
+//! {"day":14,"input":"sample","part":"one","answer":1588,"type":"number","duration_ns":51234,"error":null}
+//! ```
+
+use crate::registry::Part;
+use crate::Answer;
+use anyhow::bail;
+use serde::Serialize;
+use std::io::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    /// Human readable lines
+    Text,
+    /// A single JSON array holding every record, written once all days ran
+    Json,
+    /// One JSON object per line, written as soon as each part completes
+    Ndjson,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub input: String,
+    pub part: &'static str,
+    pub answer: Option<Answer>,
+    #[serde(rename = "type")]
+    pub kind: Option<&'static str>,
+    pub duration_ns: Option<u64>,
+    /// Error and its causes, outermost first
+    pub error: Option<Vec<String>>,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            _ => bail!("invalid format {}, expected text, json or ndjson", s),
+        }
+    }
+}
+
+impl Record {
+    #[must_use]
+    pub fn new(
+        day: u8,
+        input: &str,
+        part: Part,
+        result: Result<Answer, &anyhow::Error>,
+        duration: Option<Duration>,
+    ) -> Self {
+        let (answer, error) = match result {
+            Ok(answer) => (Some(answer), None),
+            Err(error) => (None, Some(error.chain().map(ToString::to_string).collect())),
+        };
+
+        Self {
+            day,
+            input: input.to_string(),
+            part: part.name(),
+            kind: answer.as_ref().map(|answer| match answer {
+                Answer::Number(_) => "number",
+                Answer::Text(_) => "text",
+            }),
+            answer,
+            duration_ns: duration.map(|d| d.as_nanos().try_into().unwrap_or(u64::MAX)),
+            error,
+        }
+    }
+
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Writes records in the chosen format.
+///
+/// In text mode failures are written to stderr, every other format keeps them in the records.
+pub struct Reporter<W: Write> {
+    format: Format,
+    out: W,
+    current_day: Option<u8>,
+    pending: Vec<Record>,
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(format: Format, out: W) -> Self {
+        Self {
+            format,
+            out,
+            current_day: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Reports the outcome of a part
+    ///
+    /// # Errors
+    /// If the output cannot be written an error is returned
+    pub fn report(&mut self, record: Record) -> anyhow::Result<()> {
+        match self.format {
+            Format::Text => self.write_text(&record)?,
+            Format::Json => self.pending.push(record),
+            Format::Ndjson => {
+                serde_json::to_writer(&mut self.out, &record)?;
+                writeln!(self.out)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_text(&mut self, record: &Record) -> anyhow::Result<()> {
+        if self.current_day != Some(record.day) {
+            self.current_day = Some(record.day);
+            writeln!(self.out, "Day {}", record.day)?;
+        }
+
+        match (&record.answer, &record.error) {
+            (Some(Answer::Text(text)), _) if text.contains('\n') => {
+                writeln!(
+                    self.out,
+                    "Challenge {} ({}):\n{}",
+                    record.part, record.input, text
+                )?;
+            }
+            (Some(answer), _) => {
+                writeln!(
+                    self.out,
+                    "Challenge {} ({}): {}",
+                    record.part, record.input, answer
+                )?;
+            }
+            (None, error) => eprintln!(
+                "Challenge {} ({}) failed: {}",
+                record.part,
+                record.input,
+                error.as_deref().unwrap_or_default().join(": ")
+            ),
+        }
+
+        Ok(())
+    }
+
+    /// Writes any buffered records
+    ///
+    /// # Errors
+    /// If the output cannot be written an error is returned
+    pub fn finish(mut self) -> anyhow::Result<()> {
+        if self.format == Format::Json {
+            serde_json::to_writer_pretty(&mut self.out, &self.pending)?;
+            writeln!(self.out)?;
+        }
+
+        self.out.flush()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn report(format: Format, records: Vec<Record>) -> String {
+        let mut out = Vec::new();
+        let mut reporter = Reporter::new(format, &mut out);
+
+        for record in records {
+            reporter.report(record).unwrap();
+        }
+
+        reporter.finish().unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::new(
+                14,
+                "sample",
+                Part::One,
+                Ok(Answer::Number(1588)),
+                Some(Duration::from_micros(5)),
+            ),
+            Record::new(
+                14,
+                "input",
+                Part::Two,
+                Err(&anyhow::anyhow!("No minmax found").context("challenge two")),
+                Some(Duration::from_micros(7)),
+            ),
+        ]
+    }
+
+    #[test]
+    fn ndjson_record_per_line() {
+        assert_eq!(
+            report(Format::Ndjson, records()),
+            concat!(
+                r#"{"day":14,"input":"sample","part":"one","answer":1588,"type":"number","duration_ns":5000,"error":null}"#,
+                "\n",
+                r#"{"day":14,"input":"input","part":"two","answer":null,"type":null,"duration_ns":7000,"error":["challenge two","No minmax found"]}"#,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn json_array_of_records() {
+        let output: serde_json::Value =
+            serde_json::from_str(&report(Format::Json, records())).unwrap();
+
+        assert_eq!(output.as_array().map(Vec::len), Some(2));
+        assert_eq!(output[1]["error"][1], "No minmax found");
+    }
+
+    #[test]
+    fn text_skips_failures() {
+        assert_eq!(
+            report(Format::Text, records()),
+            "Day 14\nChallenge one (sample): 1588\n"
+        );
+    }
+}
@@ -0,0 +1,342 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Generator for the skeleton of a new day.
+//!
+//! Creates `src/dayN/` from the templates next to this module, with empty inputs
+//! and answer manifest, and registers the day in `lib.rs` and in the [registry](crate::registry).
+
+use anyhow::{bail, ensure, Context};
+use std::path::{Path, PathBuf};
+
+const MOD_TEMPLATE: &str = include_str!("scaffold/mod.rs.tmpl");
+const DATA_TEMPLATE: &str = include_str!("scaffold/data.rs.tmpl");
+const ANSWERS_TEMPLATE: &str = include_str!("scaffold/answers.toml.tmpl");
+
+const DAY_PLACEHOLDER: &str = "__DAY__";
+
+fn render(template: &str, day: u8) -> String {
+    template.replace(DAY_PLACEHOLDER, &day.to_string())
+}
+
+/// Inserts `pub mod dayN;` among the other day modules, keeping them sorted as rustfmt does.
+///
+/// Returns `None` if the module is already declared.
+fn insert_module(lib: &str, day: u8) -> anyhow::Result<Option<String>> {
+    let module = format!("day{}", day);
+    let declaration = format!("pub mod {};", module);
+
+    if lib.lines().any(|line| line == declaration) {
+        return Ok(None);
+    }
+
+    let days: Vec<(usize, &str)> = lib
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            line.strip_prefix("pub mod ")
+                .and_then(|name| name.strip_suffix(';'))
+                .filter(|name| name.starts_with("day"))
+                .map(|name| (index, name))
+        })
+        .collect();
+
+    let position = match days.iter().find(|&&(_, name)| name > module.as_str()) {
+        Some(&(index, _)) => index,
+        None => match days.last() {
+            Some(&(index, _)) => index + 1,
+            None => bail!("no day module declarations found"),
+        },
+    };
+
+    Ok(Some(insert_line(lib, position, &declaration)))
+}
+
+/// Days listed in the registry source, along with the index of their line
+fn registered_days(registry: &str) -> Vec<(usize, u8)> {
+    registry
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            line.trim()
+                .strip_prefix("&crate::day")
+                .and_then(|rest| rest.strip_suffix("::DAY,"))
+                .and_then(|number| number.parse().ok())
+                .map(|number| (index, number))
+        })
+        .collect()
+}
+
+/// Inserts `&crate::dayN::DAY` in the list of registered days, in order.
+///
+/// Returns `None` if the day is already registered.
+fn insert_registration(registry: &str, day: u8) -> anyhow::Result<Option<String>> {
+    let entries = registered_days(registry);
+
+    if entries.iter().any(|&(_, number)| number == day) {
+        return Ok(None);
+    }
+
+    let position = match entries.iter().find(|&&(_, number)| number > day) {
+        Some(&(index, _)) => index,
+        None => match entries.last() {
+            Some(&(index, _)) => index + 1,
+            None => bail!("no registered days found"),
+        },
+    };
+
+    Ok(Some(insert_line(
+        registry,
+        position,
+        &format!("    &crate::day{}::DAY,", day),
+    )))
+}
+
+fn insert_line(content: &str, position: usize, line: &str) -> String {
+    let mut lines: Vec<&str> = content.lines().collect();
+    lines.insert(position, line);
+
+    let mut result = lines.join("\n");
+    if content.ends_with('\n') {
+        result.push('\n');
+    }
+
+    result
+}
+
+/// Applies an edit to a file, returning whether it changed
+fn edit_file(
+    path: &Path,
+    edit: impl FnOnce(&str) -> anyhow::Result<Option<String>>,
+) -> anyhow::Result<bool> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+
+    match edit(&content).with_context(|| format!("editing {}", path.display()))? {
+        Some(updated) => {
+            std::fs::write(path, updated).with_context(|| format!("writing {}", path.display()))?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Creates the skeleton of a day in the crate at `root` and registers it.
+///
+/// Days already in the registry of that crate are never touched. Existing inputs are never
+/// overwritten either, the source files of an unregistered day only when `force` is set.
+/// Returns the files that were created or modified.
+///
+/// # Errors
+/// If the day is not between 1 and 25, is already registered, already exists without `force`,
+/// or any file cannot be written an error is returned
+pub fn scaffold(root: &Path, day: u8, force: bool) -> anyhow::Result<Vec<PathBuf>> {
+    ensure!((1..=25).contains(&day), "day must be between 1 and 25");
+
+    let src = root.join("src");
+    let dir = src.join(format!("day{}", day));
+    let registry = src.join("registry.rs");
+
+    let registered = std::fs::read_to_string(&registry)
+        .with_context(|| format!("reading {}", registry.display()))?;
+
+    if registered_days(&registered)
+        .iter()
+        .any(|&(_, number)| number == day)
+    {
+        bail!(
+            "day {} is already registered in {}, refusing to overwrite it",
+            day,
+            registry.display()
+        );
+    }
+
+    if dir.exists() && !force {
+        bail!(
+            "day {} already exists at {}, use --force to overwrite it",
+            day,
+            dir.display()
+        );
+    }
+
+    std::fs::create_dir_all(dir.join("input"))
+        .with_context(|| format!("creating {}", dir.display()))?;
+
+    let mut changed = Vec::new();
+
+    let files = [
+        ("mod.rs", render(MOD_TEMPLATE, day), true),
+        ("data.rs", render(DATA_TEMPLATE, day), true),
+        ("input/answers.toml", ANSWERS_TEMPLATE.to_string(), false),
+        ("input/sample.txt", String::new(), false),
+        ("input/input.txt", String::new(), false),
+    ];
+
+    for (name, content, overwrite) in files {
+        let path = dir.join(name);
+
+        if path.exists() && !overwrite {
+            continue;
+        }
+
+        std::fs::write(&path, content).with_context(|| format!("writing {}", path.display()))?;
+        changed.push(path);
+    }
+
+    let lib = src.join("lib.rs");
+    if edit_file(&lib, |content| insert_module(content, day))? {
+        changed.push(lib);
+    }
+
+    if edit_file(&registry, |content| insert_registration(content, day))? {
+        changed.push(registry);
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::process::Command;
+
+    fn copy_dir(from: &Path, to: &Path) {
+        std::fs::create_dir_all(to).unwrap();
+
+        for entry in std::fs::read_dir(from).unwrap() {
+            let path = entry.unwrap().path();
+            let target = to.join(path.file_name().unwrap());
+
+            if path.is_dir() {
+                copy_dir(&path, &target);
+            } else {
+                std::fs::copy(&path, &target).unwrap();
+            }
+        }
+    }
+
+    /// Copy of this crate without day 25, to scaffold it again
+    fn crate_without_last_day() -> PathBuf {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let root = std::env::temp_dir().join(format!("aoc2021-scaffold-{}", std::process::id()));
+
+        if root.exists() {
+            std::fs::remove_dir_all(&root).unwrap();
+        }
+
+        copy_dir(&manifest_dir.join("src"), &root.join("src"));
+        std::fs::remove_dir_all(root.join("src/day25")).unwrap();
+
+        for file in ["Cargo.toml", "Cargo.lock"] {
+            if manifest_dir.join(file).exists() {
+                std::fs::copy(manifest_dir.join(file), root.join(file)).unwrap();
+            }
+        }
+
+        for (file, line) in [
+            ("src/lib.rs", "pub mod day25;\n"),
+            ("src/registry.rs", "    &crate::day25::DAY,\n"),
+        ] {
+            let path = root.join(file);
+            let content = std::fs::read_to_string(&path).unwrap();
+
+            assert!(content.contains(line));
+            std::fs::write(&path, content.replacen(line, "", 1)).unwrap();
+        }
+
+        root
+    }
+
+    #[test]
+    fn registered_days_are_kept() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+        for force in [false, true] {
+            let error = scaffold(root, 25, force).unwrap_err();
+
+            assert!(error.to_string().contains("already registered"));
+        }
+    }
+
+    #[test]
+    #[ignore = "copies the crate and runs `cargo check` on it, run with `cargo test -- --ignored`"]
+    fn scaffolded_day_compiles() {
+        let root = crate_without_last_day();
+
+        let changed = scaffold(&root, 25, false).unwrap();
+        assert_eq!(changed.len(), 7);
+        assert!(scaffold(&root, 25, true).is_err());
+
+        let status = Command::new(env!("CARGO"))
+            .args(["check", "--offline", "--quiet", "--lib", "--tests"])
+            .current_dir(&root)
+            .env(
+                "CARGO_TARGET_DIR",
+                Path::new(env!("CARGO_MANIFEST_DIR")).join("target/scaffold"),
+            )
+            .status()
+            .unwrap();
+
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert!(status.success());
+    }
+
+    #[test]
+    fn templates_are_rendered() {
+        for template in [MOD_TEMPLATE, DATA_TEMPLATE] {
+            let rendered = render(template, 19);
+
+            assert!(!rendered.contains(DAY_PLACEHOLDER));
+        }
+
+        assert!(render(MOD_TEMPLATE, 19).contains("lazy_input!(19)"));
+    }
+
+    #[test]
+    fn module_is_inserted_sorted() {
+        let lib = "pub mod registry;\n\npub mod day1;\npub mod day10;\npub mod day3;\n";
+
+        assert_eq!(
+            insert_module(lib, 2).unwrap().unwrap(),
+            "pub mod registry;\n\npub mod day1;\npub mod day10;\npub mod day2;\npub mod day3;\n"
+        );
+        assert_eq!(
+            insert_module(lib, 4).unwrap().unwrap(),
+            "pub mod registry;\n\npub mod day1;\npub mod day10;\npub mod day3;\npub mod day4;\n"
+        );
+        assert!(insert_module(lib, 10).unwrap().is_none());
+    }
+
+    #[test]
+    fn registration_is_inserted_in_order() {
+        let registry = "DAYS = &[\n    &crate::day1::DAY,\n    &crate::day3::DAY,\n];\n";
+
+        assert_eq!(
+            insert_registration(registry, 2).unwrap().unwrap(),
+            "DAYS = &[\n    &crate::day1::DAY,\n    &crate::day2::DAY,\n    &crate::day3::DAY,\n];\n"
+        );
+        assert!(insert_registration(registry, 3).unwrap().is_none());
+    }
+}
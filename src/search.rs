@@ -0,0 +1,333 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Shortest path searches over graphs described by closures.
+//!
+//! The graph is never built upfront: each search starts from a node, asks a closure for the
+//! neighbours of every node it visits and stops at the first node accepted as a goal.
+
+use rustc_hash::FxHashMap;
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Path found by a search, from the start node to the goal, both included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    pub nodes: Vec<N>,
+}
+
+/// Node reached by a search, along with how it was reached
+struct Visit<N, C> {
+    node: N,
+    parent: usize,
+    cost: C,
+}
+
+/// Nodes reached by a search, indexed to keep the queue small and the parents cheap to follow
+struct Visited<N, C> {
+    visits: Vec<Visit<N, C>>,
+    indices: FxHashMap<N, usize>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Visited<N, C> {
+    fn new(start: N, cost: C) -> Self {
+        Self {
+            indices: FxHashMap::from_iter([(start.clone(), 0)]),
+            visits: vec![Visit {
+                node: start,
+                parent: usize::MAX,
+                cost,
+            }],
+        }
+    }
+
+    /// Records that `node` was reached from `parent`, unless it was already reached more cheaply.
+    ///
+    /// Returns the index of the node if the visit was recorded.
+    fn reach(&mut self, node: N, parent: usize, cost: C) -> Option<usize>
+    where
+        C: Ord,
+    {
+        let index = self.visits.len();
+
+        match self.indices.entry(node) {
+            Entry::Vacant(entry) => {
+                self.visits.push(Visit {
+                    node: entry.key().clone(),
+                    parent,
+                    cost,
+                });
+                entry.insert(index);
+                Some(index)
+            }
+            Entry::Occupied(entry) => {
+                let visit = &mut self.visits[*entry.get()];
+
+                if cost < visit.cost {
+                    visit.parent = parent;
+                    visit.cost = cost;
+                    Some(*entry.get())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn path(&self, mut index: usize) -> Path<N, C> {
+        let cost = self.visits[index].cost;
+        let mut nodes = Vec::new();
+
+        while index != usize::MAX {
+            let visit = &self.visits[index];
+            nodes.push(visit.node.clone());
+            index = visit.parent;
+        }
+
+        nodes.reverse();
+
+        Path { cost, nodes }
+    }
+}
+
+/// Entry of the priority queue, ordered so that the lowest estimate is popped first
+struct Candidate<C> {
+    estimate: C,
+    cost: C,
+    index: usize,
+}
+
+impl<C: Ord> Ord for Candidate<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed to turn the max-heap into a min-heap,
+        // ties go to the candidate that is furthest along its path
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<C: Ord> PartialOrd for Candidate<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord> PartialEq for Candidate<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Ord> Eq for Candidate<C> {}
+
+/// Finds the cheapest path from `start` to a goal, guided by a heuristic.
+///
+/// `neighbours` returns the nodes reachable from a node with the cost of each step,
+/// and `heuristic` estimates the remaining cost to a goal. The path is only guaranteed
+/// to be the cheapest if the heuristic never overestimates that cost.
+///
+/// Returns `None` if no goal can be reached.
+pub fn astar<N, C, FN, I, FH, FG>(
+    start: N,
+    mut neighbours: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    let mut queue = BinaryHeap::new();
+    queue.push(Candidate {
+        estimate: heuristic(&start),
+        cost: C::default(),
+        index: 0,
+    });
+
+    let mut visited = Visited::new(start, C::default());
+
+    while let Some(Candidate { cost, index, .. }) = queue.pop() {
+        let visit = &visited.visits[index];
+
+        // A cheaper way to this node was found after this candidate was queued
+        if cost > visit.cost {
+            continue;
+        }
+
+        if is_goal(&visit.node) {
+            return Some(visited.path(index));
+        }
+
+        for (neighbour, step) in neighbours(&visit.node) {
+            let cost = cost + step;
+            let estimate = cost + heuristic(&neighbour);
+
+            if let Some(index) = visited.reach(neighbour, index, cost) {
+                queue.push(Candidate {
+                    estimate,
+                    cost,
+                    index,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the cheapest path from `start` to a goal.
+///
+/// `neighbours` returns the nodes reachable from a node with the cost of each step.
+///
+/// Returns `None` if no goal can be reached.
+pub fn dijkstra<N, C, FN, I, FG>(start: N, neighbours: FN, is_goal: FG) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// Finds the path with the fewest steps from `start` to a goal, the cost being the number of steps.
+///
+/// Returns `None` if no goal can be reached.
+pub fn bfs<N, FN, I, FG>(start: N, mut neighbours: FN, mut is_goal: FG) -> Option<Path<N, usize>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    let mut queue = VecDeque::from([0]);
+    let mut visited = Visited::new(start, 0);
+
+    while let Some(index) = queue.pop_front() {
+        let visit = &visited.visits[index];
+
+        if is_goal(&visit.node) {
+            return Some(visited.path(index));
+        }
+
+        let cost = visit.cost + 1;
+
+        for neighbour in neighbours(&visit.node) {
+            // The first visit of a node is always through the fewest steps
+            if visited.indices.contains_key(&neighbour) {
+                continue;
+            }
+
+            if let Some(index) = visited.reach(neighbour, index, cost) {
+                queue.push_back(index);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Directed graph where the direct edge is more expensive than the detour
+    fn edges(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('d', 10)],
+            'b' => vec![('c', 2)],
+            'c' => vec![('d', 3)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest_path() {
+        let path = dijkstra('a', edges, |&node| node == 'd').unwrap();
+
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.nodes, vec!['a', 'b', 'c', 'd']);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        assert_eq!(dijkstra('b', edges, |&node| node == 'a'), None);
+        assert_eq!(
+            bfs(
+                'b',
+                |&node| edges(&node).into_iter().map(|(n, _)| n),
+                |&node| node == 'a'
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn bfs_finds_fewest_steps() {
+        let path = bfs(
+            'a',
+            |&node| edges(&node).into_iter().map(|(n, _)| n),
+            |&node| node == 'd',
+        )
+        .unwrap();
+
+        assert_eq!(path.cost, 1);
+        assert_eq!(path.nodes, vec!['a', 'd']);
+    }
+
+    #[test]
+    fn astar_on_open_grid() {
+        let size = 10_i32;
+        let goal = (size - 1, size - 1);
+
+        let path = astar(
+            (0, 0),
+            |&(x, y): &(i32, i32)| {
+                [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                    .into_iter()
+                    .filter(|&(x, y)| (0..size).contains(&x) && (0..size).contains(&y))
+                    .map(|node| (node, 1))
+            },
+            |&(x, y)| (goal.0 - x) + (goal.1 - y),
+            |&node| node == goal,
+        )
+        .unwrap();
+
+        assert_eq!(path.cost, 18);
+        assert_eq!(path.nodes.len(), 19);
+        assert_eq!(path.nodes.first(), Some(&(0, 0)));
+        assert_eq!(path.nodes.last(), Some(&goal));
+    }
+}
@@ -0,0 +1,260 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Verification of the solutions against the expected answers of each day.
+//!
+//! The expected answers are stored next to the inputs of each day, in `answers.toml`:
+//!
+//! ```toml
+//! [sample]
+//! one = 7
+//! two = 5
+//! ```
+
+use crate::input::{is_not_found, InputProvider};
+use crate::registry::{Day, Part};
+use crate::Answer;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+pub const MANIFEST_NAME: &str = "answers.toml";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ExpectedAnswers {
+    pub one: Option<Answer>,
+    pub two: Option<Answer>,
+}
+
+/// Expected answers of a day, keyed by the name of the input
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Manifest(BTreeMap<String, ExpectedAnswers>);
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: u8,
+    pub input: String,
+    pub part: Part,
+    pub expected: Answer,
+    pub actual: anyhow::Result<Answer>,
+}
+
+impl ExpectedAnswers {
+    #[must_use]
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.one.as_ref(),
+            Part::Two => self.two.as_ref(),
+        }
+    }
+}
+
+impl Manifest {
+    /// Loads the answer manifest of the given day, if it has one
+    ///
+    /// # Errors
+    /// If the manifest exists but is not valid an error is returned
+    pub fn load(day: &Day, inputs: &impl InputProvider) -> anyhow::Result<Option<Self>> {
+        let content = match inputs.get_input(MANIFEST_NAME) {
+            Ok(content) => content,
+            Err(e) if is_not_found(&e) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| anyhow::anyhow!("invalid answer manifest for day {}: {}", day.number, e))
+    }
+
+    /// Iterates over the expected answers, ordering the inputs as the day runs them by default
+    pub fn iter<'a>(&'a self, day: &Day) -> impl Iterator<Item = (&'a str, &'a ExpectedAnswers)> {
+        let mut entries: Vec<_> = self.0.iter().map(|(k, v)| (k.as_str(), v)).collect();
+
+        entries.sort_by_key(|&(name, _)| {
+            day.default_inputs
+                .iter()
+                .position(|&default| default == name)
+                .unwrap_or(usize::MAX)
+        });
+
+        entries.into_iter()
+    }
+}
+
+impl Outcome {
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        match &self.actual {
+            Ok(actual) => answers_match(&self.expected, actual),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Compares two answers, ignoring trailing whitespace on each line of text answers
+fn answers_match(expected: &Answer, actual: &Answer) -> bool {
+    match (expected, actual) {
+        (Answer::Text(expected), Answer::Text(actual)) => expected
+            .trim_end()
+            .lines()
+            .map(str::trim_end)
+            .eq(actual.trim_end().lines().map(str::trim_end)),
+        _ => expected == actual,
+    }
+}
+
+/// Runs the solution of a day against every input listed in its manifest
+///
+/// # Errors
+/// If the manifest cannot be loaded an error is returned.
+/// Failures to read or solve an input are reported as failed outcomes instead.
+pub fn verify_day(day: &Day, inputs: &impl InputProvider) -> anyhow::Result<Option<Vec<Outcome>>> {
+    let manifest = match Manifest::load(day, inputs)? {
+        Some(manifest) => manifest,
+        None => return Ok(None),
+    };
+
+    let mut outcomes = Vec::new();
+
+    for (input, expected) in manifest.iter(day) {
+        let parsed = day
+            .read_input(inputs, input)
+            .and_then(|content| day.parse(&content));
+
+        for part in Part::ALL {
+            let expected = match expected.get(part) {
+                Some(expected) => expected.clone(),
+                None => continue,
+            };
+
+            let actual = match &parsed {
+                Ok(parsed) => day.solve(part, parsed.as_ref()),
+                Err(e) => Err(anyhow::anyhow!("{:#}", e)),
+            };
+
+            outcomes.push(Outcome {
+                day: day.number,
+                input: input.to_string(),
+                part,
+                expected,
+                actual,
+            });
+        }
+    }
+
+    Ok(Some(outcomes))
+}
+
+/// Table listing the outcomes that did not match the expected answers
+pub struct DiffTable<'a>(pub &'a [Outcome]);
+
+/// Shortens an answer to fit in a single table cell
+fn cell(text: &str) -> String {
+    const WIDTH: usize = 32;
+
+    let escaped: String = text.escape_debug().collect();
+
+    if escaped.chars().count() > WIDTH {
+        escaped
+            .chars()
+            .take(WIDTH - 3)
+            .chain("...".chars())
+            .collect()
+    } else {
+        escaped
+    }
+}
+
+impl Display for DiffTable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>3}  {:<10} {:<4} {:<32} {:<32}",
+            "Day", "Input", "Part", "Expected", "Actual"
+        )?;
+
+        for outcome in self.0.iter().filter(|outcome| !outcome.is_success()) {
+            let actual = match &outcome.actual {
+                Ok(actual) => cell(&actual.to_string()),
+                Err(e) => cell(&format!("error: {:#}", e)),
+            };
+
+            writeln!(
+                f,
+                "{:>3}  {:<10} {:<4} {:<32} {:<32}",
+                outcome.day,
+                outcome.input,
+                outcome.part.name(),
+                cell(&outcome.expected.to_string()),
+                actual,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_manifest() {
+        let manifest: Manifest = toml::from_str(
+            r##"
+            [sample]
+            one = 7
+            two = "#\n#"
+
+            [input]
+            one = 1681
+            "##,
+        )
+        .unwrap();
+
+        let sample = &manifest.0["sample"];
+        assert_eq!(sample.one, Some(Answer::Number(7)));
+        assert_eq!(sample.two, Some(Answer::Text("#\n#".to_string())));
+        assert_eq!(manifest.0["input"].two, None);
+    }
+
+    #[test]
+    fn text_answers_ignore_trailing_whitespace() {
+        let expected = Answer::Text("# #\n # ".to_string());
+
+        assert!(answers_match(
+            &expected,
+            &Answer::Text("# #  \n #\n".to_string())
+        ));
+        assert!(!answers_match(
+            &expected,
+            &Answer::Text("###\n #".to_string())
+        ));
+        assert!(!answers_match(
+            &Answer::Number(1),
+            &Answer::Text("1".to_string())
+        ));
+    }
+}
@@ -0,0 +1,303 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Terminal visualization of the simulations run by the solutions
+//!
+//! Days opt in by returning an [`Animation`] from [`Solution::animate`](crate::Solution::animate),
+//! which is then played back by [`play`] in the terminal or saved to a file by [`record`].
+
+pub mod record;
+
+use crate::grid::Grid;
+use anyhow::Context;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+use termion::screen::AlternateScreen;
+use tui::backend::{Backend, TermionBackend};
+use tui::layout::{Constraint, Direction, Layout};
+use tui::style::{Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Paragraph};
+use tui::Terminal;
+
+pub use tui::style::Color;
+
+/// Single character of a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    pub symbol: char,
+    pub color: Color,
+}
+
+/// Picture of the state of a simulation at some point
+#[derive(Debug, Clone)]
+pub struct Frame {
+    /// Short description of the state, shown above the picture
+    pub caption: String,
+    pub cells: Grid<Glyph>,
+}
+
+/// Source of the frames of a visualization
+pub trait Animation {
+    /// Advances the simulation, returning `None` once it is over
+    fn next_frame(&mut self) -> Option<Frame>;
+}
+
+impl<I: Iterator<Item = Frame>> Animation for I {
+    fn next_frame(&mut self) -> Option<Frame> {
+        self.next()
+    }
+}
+
+impl Glyph {
+    #[must_use]
+    pub const fn new(symbol: char, color: Color) -> Self {
+        Self { symbol, color }
+    }
+}
+
+/// Fastest playback speed, in frames per second
+const MAX_FPS: u32 = 240;
+
+/// Playback state controlled from the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Playback {
+    paused: bool,
+    fps: u32,
+}
+
+/// Effect of a key press on the playback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    None,
+    Advance,
+    Quit,
+}
+
+impl Playback {
+    fn new(fps: u32) -> Self {
+        Self {
+            paused: false,
+            fps: fps.clamp(1, MAX_FPS),
+        }
+    }
+
+    fn handle(&mut self, key: Key) -> Action {
+        match key {
+            Key::Char(' ') => self.paused = !self.paused,
+            Key::Char('n') | Key::Right => {
+                self.paused = true;
+                return Action::Advance;
+            }
+            Key::Char('+') | Key::Char('=') | Key::Up => self.fps = (self.fps * 2).min(MAX_FPS),
+            Key::Char('-') | Key::Down => self.fps = (self.fps / 2).max(1),
+            Key::Char('q') | Key::Esc | Key::Ctrl('c') => return Action::Quit,
+            _ => {}
+        }
+
+        Action::None
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(1) / self.fps
+    }
+}
+
+/// Frame being shown along with where it is in the animation
+struct Screen<'a> {
+    title: &'a str,
+    frame: Frame,
+    index: usize,
+    finished: bool,
+}
+
+/// Renders the rows of glyphs, merging runs of the same color into a single span
+fn spans<'a>(rows: impl Iterator<Item = &'a [Glyph]>) -> Vec<Spans<'static>> {
+    rows.map(|row| {
+        let mut spans = Vec::new();
+
+        for run in row.chunk_by(|a, b| a.color == b.color) {
+            let text: String = run.iter().map(|glyph| glyph.symbol).collect();
+            spans.push(Span::styled(text, Style::default().fg(run[0].color)));
+        }
+
+        Spans::from(spans)
+    })
+    .collect()
+}
+
+impl Screen<'_> {
+    fn draw<B: Backend>(
+        &self,
+        terminal: &mut Terminal<B>,
+        playback: &Playback,
+    ) -> anyhow::Result<()> {
+        let state = if self.finished {
+            "finished"
+        } else if playback.paused {
+            "paused"
+        } else {
+            "playing"
+        };
+
+        let status = format!(
+            "frame {} | {} fps | {} | space: play/pause  n: step  +/-: speed  q: quit",
+            self.index, playback.fps, state
+        );
+
+        terminal.draw(|f| {
+            let areas = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)])
+                .split(f.size());
+
+            let block = Block::default().borders(Borders::ALL).title(Span::styled(
+                format!(" {} — {} ", self.title, self.frame.caption),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+
+            // Only the part of the frame that fits on screen is rendered
+            let inner = block.inner(areas[0]);
+            let cells = &self.frame.cells;
+            let visible = cells.view(
+                (0, 0),
+                cells.width().min(usize::from(inner.width)),
+                cells.height().min(usize::from(inner.height)),
+            );
+
+            f.render_widget(Paragraph::new(spans(visible.rows())).block(block), areas[0]);
+            f.render_widget(Paragraph::new(status), areas[1]);
+        })?;
+
+        Ok(())
+    }
+}
+
+/// Plays an animation in the terminal until it is closed
+///
+/// # Errors
+/// An error is returned if the standard output is not a terminal, if it cannot be drawn to or if
+/// the animation has no frames.
+pub fn play(title: &str, mut animation: Box<dyn Animation>, fps: u32) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        termion::is_tty(&std::io::stdout()),
+        "visualizations need the output to be a terminal"
+    );
+
+    let frame = animation
+        .next_frame()
+        .context("the animation has no frames")?;
+
+    let stdout = std::io::stdout()
+        .into_raw_mode()
+        .context("switching the terminal to raw mode")?;
+    let mut terminal = Terminal::new(TermionBackend::new(AlternateScreen::from(stdout)))?;
+    terminal.hide_cursor()?;
+
+    // Reading keys blocks, so it happens on its own thread that is left behind once playback ends
+    let (keys, key_presses) = mpsc::channel();
+    std::thread::spawn(move || {
+        for key in std::io::stdin().keys().flatten() {
+            if keys.send(key).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut playback = Playback::new(fps);
+    let mut screen = Screen {
+        title,
+        frame,
+        index: 1,
+        finished: false,
+    };
+    let mut last_frame = Instant::now();
+
+    loop {
+        screen.draw(&mut terminal, &playback)?;
+
+        let timeout = if playback.paused || screen.finished {
+            Duration::from_secs(3600)
+        } else {
+            playback.interval().saturating_sub(last_frame.elapsed())
+        };
+
+        let advance = match key_presses.recv_timeout(timeout) {
+            Ok(key) => match playback.handle(key) {
+                Action::None => false,
+                Action::Advance => true,
+                Action::Quit => break,
+            },
+            Err(RecvTimeoutError::Timeout) => !playback.paused,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        if advance && !screen.finished {
+            match animation.next_frame() {
+                Some(frame) => {
+                    screen.frame = frame;
+                    screen.index += 1;
+                }
+                None => screen.finished = true,
+            }
+
+            last_frame = Instant::now();
+        }
+    }
+
+    terminal.show_cursor()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(&[Key::Char(' ')] => (true, 10, Action::None) ; "pause")]
+    #[test_case(&[Key::Char(' '), Key::Char(' ')] => (false, 10, Action::None) ; "resume")]
+    #[test_case(&[Key::Char('n')] => (true, 10, Action::Advance) ; "step pauses")]
+    #[test_case(&[Key::Char('+'), Key::Up] => (false, 40, Action::None) ; "faster")]
+    #[test_case(&[Key::Char('-'), Key::Char('-'), Key::Down, Key::Down] => (false, 1, Action::None) ; "slowest")]
+    #[test_case(&[Key::Char('q')] => (false, 10, Action::Quit) ; "quit")]
+    fn controls(keys: &[Key]) -> (bool, u32, Action) {
+        let mut playback = Playback::new(10);
+        let action = keys.iter().map(|&key| playback.handle(key)).last().unwrap();
+
+        (playback.paused, playback.fps, action)
+    }
+
+    #[test]
+    fn fastest() {
+        let mut playback = Playback::new(1000);
+
+        assert_eq!(playback.fps, MAX_FPS);
+        playback.handle(Key::Char('+'));
+        assert_eq!(playback.fps, MAX_FPS);
+    }
+}
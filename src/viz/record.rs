@@ -0,0 +1,403 @@
+/*
+ * MIT License
+ *
+ * Copyright (c) 2021 Luiz Ferraz
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+
+//! Recording of animations to files that can be shared and replayed without the solutions
+//!
+//! Two formats are supported:
+//! - [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/), replaying the frames in a
+//!   terminal with ANSI colors
+//! - animated GIF, drawing every glyph as a square of its color from a fixed palette
+
+use super::{Animation, Color, Frame, Glyph};
+use anyhow::Context;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// File format of a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    Asciicast,
+    Gif,
+}
+
+impl RecordFormat {
+    /// Guesses the format from the extension of a file
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for RecordFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cast" | "asciicast" => Ok(Self::Asciicast),
+            "gif" => Ok(Self::Gif),
+            _ => anyhow::bail!("unknown recording format {:?}, expected `cast` or `gif`", s),
+        }
+    }
+}
+
+/// Frames captured from an animation, ready to be written in any format
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub title: String,
+    pub frames: Vec<Frame>,
+    /// Playback speed in frames per second
+    pub fps: u32,
+}
+
+impl Recording {
+    /// Captures the frames of an animation, stopping after `limit` frames
+    pub fn capture(title: &str, animation: &mut dyn Animation, fps: u32, limit: usize) -> Self {
+        let frames = std::iter::from_fn(|| animation.next_frame())
+            .take(limit)
+            .collect();
+
+        Self {
+            title: title.to_string(),
+            frames,
+            fps: fps.max(1),
+        }
+    }
+
+    /// Width and height in cells large enough for every frame
+    fn size(&self) -> (usize, usize) {
+        self.frames.iter().fold((0, 0), |(width, height), frame| {
+            (
+                width.max(frame.cells.width()),
+                height.max(frame.cells.height()),
+            )
+        })
+    }
+
+    /// Writes the recording to a file
+    ///
+    /// # Errors
+    /// An error is returned if the recording is empty or the file cannot be written
+    pub fn save(&self, path: &Path, format: RecordFormat) -> anyhow::Result<()> {
+        anyhow::ensure!(!self.frames.is_empty(), "there are no frames to record");
+
+        let mut file = BufWriter::new(
+            File::create(path).with_context(|| format!("creating {}", path.display()))?,
+        );
+
+        match format {
+            RecordFormat::Asciicast => self.write_asciicast(&mut file)?,
+            RecordFormat::Gif => self.write_gif(&mut file)?,
+        }
+
+        file.flush()
+            .with_context(|| format!("writing {}", path.display()))
+    }
+
+    /// Writes the header and one output event per frame, each redrawing the whole screen
+    fn write_asciicast(&self, out: &mut impl Write) -> anyhow::Result<()> {
+        let (width, height) = self.size();
+
+        let header = serde_json::json!({
+            "version": 2,
+            "width": width.max(self.title.chars().count()),
+            // One line for the caption above the frame
+            "height": height + 1,
+            "title": self.title,
+            "env": { "TERM": "xterm-256color" },
+        });
+        writeln!(out, "{}", header)?;
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            let time = index as f64 / f64::from(self.fps);
+            let event = (time, "o", ansi_screen(frame));
+
+            writeln!(out, "{}", serde_json::to_string(&event)?)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_gif(&self, out: &mut impl Write) -> anyhow::Result<()> {
+        let (width, height) = self.size();
+        anyhow::ensure!(width > 0 && height > 0, "the frames are empty");
+
+        // Keep the images around a thousand pixels wide or tall, with visible cells
+        let scale = (MAX_GIF_SIZE / width.max(height)).clamp(1, MAX_CELL_PIXELS);
+        let (pixels_wide, pixels_tall) = (width * scale, height * scale);
+
+        let to_u16 = |size: usize| {
+            u16::try_from(size).with_context(|| format!("{} pixels is too large for a GIF", size))
+        };
+
+        let palette: Vec<u8> = PALETTE.iter().flatten().copied().collect();
+        let mut encoder =
+            gif::Encoder::new(out, to_u16(pixels_wide)?, to_u16(pixels_tall)?, &palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        // GIF delays are in hundredths of a second
+        let delay = u16::try_from(100 / self.fps).unwrap_or(u16::MAX).max(1);
+
+        for frame in &self.frames {
+            let mut pixels = vec![BACKGROUND; pixels_wide * pixels_tall];
+
+            for ((x, y), glyph) in frame.cells.iter() {
+                let index = palette_index(glyph);
+
+                for row in y * scale..(y + 1) * scale {
+                    pixels[row * pixels_wide + x * scale..row * pixels_wide + (x + 1) * scale]
+                        .fill(index);
+                }
+            }
+
+            let mut image = gif::Frame::from_indexed_pixels(
+                to_u16(pixels_wide)?,
+                to_u16(pixels_tall)?,
+                &pixels,
+                None,
+            );
+            image.delay = delay;
+
+            encoder.write_frame(&image)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Largest side of a GIF recording in pixels, unless every cell already takes a single pixel
+const MAX_GIF_SIZE: usize = 1024;
+
+/// Largest side of a cell in a GIF recording
+const MAX_CELL_PIXELS: usize = 8;
+
+/// Palette index used for blank cells and around frames smaller than the recording
+const BACKGROUND: u8 = 0;
+
+/// Colors of a GIF recording, the 16 standard terminal colors in the order of their ANSI codes
+const PALETTE: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [205, 49, 49],
+    [13, 188, 121],
+    [229, 229, 16],
+    [36, 114, 200],
+    [188, 63, 188],
+    [17, 168, 205],
+    [204, 204, 204],
+    [102, 102, 102],
+    [241, 76, 76],
+    [35, 209, 139],
+    [245, 245, 67],
+    [59, 142, 234],
+    [214, 112, 214],
+    [41, 184, 219],
+    [255, 255, 255],
+];
+
+/// Index of a color among the 16 standard terminal colors, if it is one of them
+fn standard_index(color: Color) -> Option<u8> {
+    Some(match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Indexed(index) if index < 16 => index,
+        _ => return None,
+    })
+}
+
+/// Approximate RGB value of any terminal color, following the xterm 256 color palette
+fn rgb(color: Color) -> [u8; 3] {
+    if let Some(index) = standard_index(color) {
+        return PALETTE[usize::from(index)];
+    }
+
+    match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Indexed(index @ 16..=231) => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+
+            [level(index / 36), level(index / 6 % 6), level(index % 6)]
+        }
+        Color::Indexed(index) => {
+            let gray = 8 + (index.saturating_sub(232)) * 10;
+            [gray; 3]
+        }
+        // Reset leaves the default foreground, which is light on most terminals
+        _ => PALETTE[7],
+    }
+}
+
+/// Closest entry of the palette to the color of a glyph, blank glyphs being background
+fn palette_index(glyph: &Glyph) -> u8 {
+    if glyph.symbol == ' ' {
+        return BACKGROUND;
+    }
+
+    let [r, g, b] = rgb(glyph.color).map(i32::from);
+
+    (0..PALETTE.len())
+        .min_by_key(|&index| {
+            let [pr, pg, pb] = PALETTE[index].map(i32::from);
+            (r - pr).pow(2) + (g - pg).pow(2) + (b - pb).pow(2)
+        })
+        .map_or(BACKGROUND, |index| index as u8)
+}
+
+/// ANSI escape sequence selecting a foreground color
+fn ansi_color(color: Color) -> String {
+    match (standard_index(color), color) {
+        (Some(index @ 0..=7), _) => format!("\x1b[{}m", 30 + index),
+        (Some(index), _) => format!("\x1b[{}m", 90 + index - 8),
+        (None, Color::Rgb(r, g, b)) => format!("\x1b[38;2;{};{};{}m", r, g, b),
+        (None, Color::Indexed(index)) => format!("\x1b[38;5;{}m", index),
+        (None, _) => "\x1b[39m".to_string(),
+    }
+}
+
+/// Terminal output clearing the screen and drawing the caption followed by the frame
+fn ansi_screen(frame: &Frame) -> String {
+    let mut screen = format!("\x1b[H\x1b[2J\x1b[1m{}\x1b[0m\r\n", frame.caption);
+
+    for (y, row) in frame.cells.rows().enumerate() {
+        if y > 0 {
+            screen.push_str("\r\n");
+        }
+
+        for run in row.chunk_by(|a, b| a.color == b.color) {
+            screen.push_str(&ansi_color(run[0].color));
+            screen.extend(run.iter().map(|glyph| glyph.symbol));
+        }
+
+        screen.push_str("\x1b[0m");
+    }
+
+    screen
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::Grid;
+    use test_case::test_case;
+
+    fn frames() -> Vec<Frame> {
+        (1..=3)
+            .map(|size| Frame {
+                caption: format!("size {}", size),
+                cells: Grid::filled(size, size, Glyph::new('#', Color::Yellow)),
+            })
+            .collect()
+    }
+
+    #[test_case(Color::Yellow => "\x1b[33m" ; "standard")]
+    #[test_case(Color::LightCyan => "\x1b[96m" ; "bright")]
+    #[test_case(Color::Indexed(208) => "\x1b[38;5;208m" ; "indexed")]
+    #[test_case(Color::Rgb(1, 2, 3) => "\x1b[38;2;1;2;3m" ; "rgb")]
+    fn ansi(color: Color) -> String {
+        ansi_color(color)
+    }
+
+    #[test_case(Glyph::new('#', Color::Yellow) => 3 ; "standard")]
+    #[test_case(Glyph::new(' ', Color::Yellow) => BACKGROUND ; "blank")]
+    #[test_case(Glyph::new('#', Color::Rgb(250, 250, 250)) => 15 ; "nearest")]
+    #[test_case(Glyph::new('#', Color::Indexed(196)) => 1 ; "color cube")]
+    fn palette(glyph: Glyph) -> u8 {
+        palette_index(&glyph)
+    }
+
+    #[test]
+    fn capture_limit() {
+        let recording = Recording::capture("test", &mut frames().into_iter(), 10, 2);
+
+        assert_eq!(recording.frames.len(), 2);
+        assert_eq!(recording.size(), (2, 2));
+
+        let mut endless = frames().into_iter().cycle();
+        let recording = Recording::capture("test", &mut endless, 10, 1000);
+
+        assert_eq!(recording.frames.len(), 1000);
+    }
+
+    #[test]
+    fn asciicast() {
+        let recording = Recording::capture("test", &mut frames().into_iter(), 4, 10);
+        let mut out = Vec::new();
+
+        recording.write_asciicast(&mut out).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["height"], 4);
+        assert_eq!(lines[2][0], 0.25);
+        assert_eq!(lines[2][1], "o");
+        assert!(lines[3][2]
+            .as_str()
+            .unwrap()
+            .contains("size 3\x1b[0m\r\n\x1b[33m###"));
+    }
+
+    #[test]
+    fn gif() {
+        let recording = Recording::capture("test", &mut frames().into_iter(), 10, 10);
+        let mut out = Vec::new();
+
+        recording.write_gif(&mut out).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(out.as_slice()).unwrap();
+
+        let scale = MAX_CELL_PIXELS as u16;
+        assert_eq!((decoder.width(), decoder.height()), (3 * scale, 3 * scale));
+
+        let mut count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            count += 1;
+
+            assert_eq!(frame.delay, 10);
+            assert_eq!(frame.buffer[0], 3);
+        }
+        assert_eq!(count, 3);
+    }
+}